@@ -0,0 +1,640 @@
+use std::{error::Error, fmt::Debug, fmt::Display};
+
+use super::hands::{PlayerId, TrickTakingGame, MAX_PLAYERS};
+use crate::error::ShuftError;
+
+/// The rules of the auction played before the tricks of a hand, in games
+/// like bridge, skat or belote. The winner of the auction becomes the
+/// declarer, and their bid becomes the `Contract` of the hand, which can be
+/// stored in its `HandContext` to be used while playing and scoring. `Game`
+/// does it for the auction returned by `GameRules::auction`.
+pub trait AuctionRules: TrickTakingGame {
+    /// Whether players can double the bids of their opponents and redouble
+    /// the doubles of their bids, like in bridge.
+    const DOUBLES: bool = false;
+
+    /// Whether players who pass are out of the auction, like in skat, instead
+    /// of being able to call again when their turn comes back, like in
+    /// bridge.
+    const PASS_IS_FINAL: bool = false;
+
+    /// Whether `bid` can be called when `highest` is the highest bid so far,
+    /// if any.
+    fn is_sufficient(bid: &Self::Bid, highest: Option<&Self::Bid>) -> bool;
+
+    /// The contract played when `bidder` wins the auction with `bid`. The
+    /// `calls` of the auction, in order, are given for games where the
+    /// declarer isn't always the bidder, like bridge, where it's the first
+    /// player of the winning side who named the strain of the contract.
+    fn contract(
+        bidder: PlayerId<Self>,
+        bid: Self::Bid,
+        doubling: Doubling,
+        calls: &[(PlayerId<Self>, Call<Self::Bid>)],
+    ) -> Self::Contract;
+}
+
+/// A call made by a player during an auction.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Call<B> {
+    /// The player doesn't bid.
+    Pass,
+    /// The player bids, overcalling the highest bid so far.
+    Bid(B),
+    /// The player doubles the highest bid, made by an opponent.
+    Double,
+    /// The player redoubles the double of an opponent.
+    Redouble,
+}
+
+/// Whether the highest bid of an auction has been doubled.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Doubling {
+    /// The bid hasn't been doubled.
+    #[default]
+    Undoubled,
+    /// The bid has been doubled by an opponent of the bidder.
+    Doubled,
+    /// The double has been redoubled by the side of the bidder.
+    Redoubled,
+}
+
+/// The reasons why a call can't be made in an auction.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CallError {
+    /// It's not the turn of the player.
+    WrongTurn {
+        /// The player whose turn it is.
+        expected: usize,
+        /// The player who tried to call.
+        found: usize,
+    },
+    /// The bid isn't higher than the highest bid so far.
+    InsufficientBid,
+    /// There's no bid of an opponent that can be doubled, or the game
+    /// doesn't allow doubles.
+    CannotDouble,
+    /// There's no double of an opponent that can be redoubled, or the game
+    /// doesn't allow doubles.
+    CannotRedouble,
+    /// The auction is already over.
+    AuctionComplete,
+}
+
+impl Display for CallError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            CallError::WrongTurn { expected, found } => write!(
+                f,
+                "it's the turn of player {}, not of player {}",
+                expected, found
+            ),
+            CallError::InsufficientBid => write!(f, "the bid is not higher than the highest bid"),
+            CallError::CannotDouble => write!(f, "there is no bid of an opponent to double"),
+            CallError::CannotRedouble => write!(f, "there is no double of an opponent to redouble"),
+            CallError::AuctionComplete => write!(f, "the auction is over"),
+        }
+    }
+}
+
+impl Error for CallError {}
+
+/// An auction, where players make calls in turn starting from the player
+/// after the dealer, until the highest bid is followed by a pass of every
+/// other player, or every player passes without bidding. With
+/// `AuctionRules::PASS_IS_FINAL`, the players who passed are skipped, and the
+/// auction is over when every other player passed.
+///
+/// # Examples
+///
+/// ```
+/// use shuftlib::common::{
+///     auction::{Auction, AuctionRules, Call, Doubling},
+///     cards::FrenchCard,
+///     hands::{HandContext, PlayerId, TrickTakingGame},
+/// };
+///
+/// /// Whist with a minimal auction: players bid the number of tricks they
+/// /// will take.
+/// #[derive(Debug, Clone, Copy)]
+/// struct BiddingWhist;
+///
+/// impl TrickTakingGame for BiddingWhist {
+///     type CardType = FrenchCard;
+///     type Contract = (usize, u8);
+///     type Bid = u8;
+///
+///     const PLAYERS: usize = 4;
+///     const TRICKS: usize = 13;
+///
+///     fn determine_taker(_: &[FrenchCard], first: PlayerId<Self>, _: &HandContext<Self>) -> PlayerId<Self> {
+///         first
+///     }
+/// }
+///
+/// impl AuctionRules for BiddingWhist {
+///     fn is_sufficient(bid: &u8, highest: Option<&u8>) -> bool {
+///         highest.map_or(*bid >= 7, |highest| bid > highest)
+///     }
+///
+///     fn contract(bidder: PlayerId<Self>, bid: u8, _: Doubling, _: &[(PlayerId<Self>, Call<u8>)]) -> (usize, u8) {
+///         (*bidder, bid)
+///     }
+/// }
+///
+/// let mut auction = Auction::<BiddingWhist>::new(PlayerId::new(3).unwrap());
+/// let calls = [Call::Bid(7), Call::Pass, Call::Bid(8), Call::Pass, Call::Pass, Call::Pass];
+/// for call in calls {
+///     auction.call(auction.next_to_call().unwrap(), call).unwrap();
+/// }
+///
+/// let contract = auction.finish().unwrap().unwrap();
+/// assert_eq!(contract, (2, 8));
+/// let context = HandContext::<BiddingWhist>::new().with_contract(contract);
+/// ```
+#[derive(Debug)]
+pub struct Auction<G: TrickTakingGame> {
+    dealer: PlayerId<G>,
+    next_to_call: Option<PlayerId<G>>,
+    calls: Vec<(PlayerId<G>, Call<G::Bid>)>,
+    highest: Option<(PlayerId<G>, G::Bid)>,
+    doubling: Doubling,
+    passed: [bool; MAX_PLAYERS],
+    passes: usize,
+}
+
+impl<G: TrickTakingGame> Clone for Auction<G> {
+    fn clone(&self) -> Self {
+        Self {
+            dealer: self.dealer,
+            next_to_call: self.next_to_call,
+            calls: self.calls.clone(),
+            highest: self.highest,
+            doubling: self.doubling,
+            passed: self.passed,
+            passes: self.passes,
+        }
+    }
+}
+
+impl<G: AuctionRules> Auction<G> {
+    /// Creates an auction for the hand dealt by `dealer`, with the number of
+    /// players of its `PlayerId`. The player after the dealer calls first.
+    pub fn new(dealer: PlayerId<G>) -> Self {
+        let mut first = dealer;
+        first.inc();
+
+        Self {
+            dealer,
+            next_to_call: Some(first),
+            calls: Vec::new(),
+            highest: None,
+            doubling: Doubling::Undoubled,
+            passed: [false; MAX_PLAYERS],
+            passes: 0,
+        }
+    }
+
+    /// Whether the player whose turn it is can make `call`.
+    pub fn is_legal(&self, call: &Call<G::Bid>) -> bool {
+        self.check(call).is_ok()
+    }
+
+    fn check(&self, call: &Call<G::Bid>) -> Result<(), CallError> {
+        let player = self.next_to_call.ok_or(CallError::AuctionComplete)?;
+        let teams = G::teams(player.players());
+        let bidder_side = self
+            .highest
+            .map(|(bidder, _)| teams.are_partners(*bidder, *player));
+
+        match call {
+            Call::Pass => Ok(()),
+            Call::Bid(bid) => G::is_sufficient(bid, self.highest.as_ref().map(|(_, b)| b))
+                .then_some(())
+                .ok_or(CallError::InsufficientBid),
+            Call::Double => {
+                (G::DOUBLES && self.doubling == Doubling::Undoubled && bidder_side == Some(false))
+                    .then_some(())
+                    .ok_or(CallError::CannotDouble)
+            }
+            Call::Redouble => {
+                (G::DOUBLES && self.doubling == Doubling::Doubled && bidder_side == Some(true))
+                    .then_some(())
+                    .ok_or(CallError::CannotRedouble)
+            }
+        }
+    }
+
+    /// Makes `call` for `player`, checking that it's their turn and that the
+    /// call is legal.
+    ///
+    /// # Errors
+    ///
+    /// Fails with `ShuftError::IllegalCall` if the call can't be made, in
+    /// which case the auction isn't modified.
+    pub fn call(&mut self, player: PlayerId<G>, call: Call<G::Bid>) -> Result<(), ShuftError> {
+        let expected = self.next_to_call.ok_or(CallError::AuctionComplete)?;
+        if expected != player {
+            return Err(CallError::WrongTurn {
+                expected: *expected,
+                found: *player,
+            }
+            .into());
+        }
+        self.check(&call)?;
+
+        match call {
+            Call::Pass => {
+                self.passed[*player] = true;
+                self.passes += 1;
+            }
+            Call::Bid(bid) => {
+                self.highest = Some((player, bid));
+                self.doubling = Doubling::Undoubled;
+                self.passes = 0;
+            }
+            Call::Double => {
+                self.doubling = Doubling::Doubled;
+                self.passes = 0;
+            }
+            Call::Redouble => {
+                self.doubling = Doubling::Redoubled;
+                self.passes = 0;
+            }
+        }
+        self.calls.push((player, call));
+        self.next_to_call = (!self.ends()).then(|| self.after(player));
+
+        Ok(())
+    }
+
+    /// Whether the calls made so far end the auction.
+    fn ends(&self) -> bool {
+        let players = self.dealer.players();
+        match self.highest {
+            None => self.passes >= players,
+            Some((bidder, _)) if G::PASS_IS_FINAL => {
+                (0..players).all(|seat| seat == *bidder || self.passed[seat])
+            }
+            Some(_) => self.passes + 1 >= players,
+        }
+    }
+
+    /// The player who calls after `player`, skipping the ones who passed if
+    /// passing is final.
+    fn after(&self, mut player: PlayerId<G>) -> PlayerId<G> {
+        for _ in 0..player.players() {
+            player.inc();
+            if !(G::PASS_IS_FINAL && self.passed[*player]) {
+                break;
+            }
+        }
+        player
+    }
+
+    /// Ends the auction, returning the contract of the hand, or None if every
+    /// player passed without bidding, in which case the hand is usually
+    /// dealt again.
+    ///
+    /// # Errors
+    ///
+    /// Fails with `ShuftError::IncompleteAuction` if the auction isn't over.
+    pub fn finish(self) -> Result<Option<G::Contract>, ShuftError> {
+        if !self.is_complete() {
+            return Err(ShuftError::IncompleteAuction);
+        }
+
+        Ok(self
+            .highest
+            .map(|(bidder, bid)| G::contract(bidder, bid, self.doubling, &self.calls)))
+    }
+}
+
+impl<G: TrickTakingGame> Auction<G> {
+    /// The dealer of the hand the auction is for.
+    pub fn dealer(&self) -> PlayerId<G> {
+        self.dealer
+    }
+
+    /// The player whose turn it is, or None if the auction is over.
+    pub fn next_to_call(&self) -> Option<PlayerId<G>> {
+        self.next_to_call
+    }
+
+    /// Whether the auction is over.
+    pub fn is_complete(&self) -> bool {
+        self.next_to_call.is_none()
+    }
+
+    /// The calls made so far, in order, with the player who made them.
+    pub fn calls(&self) -> &[(PlayerId<G>, Call<G::Bid>)] {
+        &self.calls
+    }
+
+    /// The highest bid so far, with the player who made it.
+    pub fn highest_bid(&self) -> Option<(PlayerId<G>, G::Bid)> {
+        self.highest
+    }
+
+    /// Whether the highest bid has been doubled or redoubled.
+    pub fn doubling(&self) -> Doubling {
+        self.doubling
+    }
+}
+
+#[cfg(feature = "serde")]
+mod serde_impls {
+    use serde::{
+        de::Error as _, ser::SerializeStruct, Deserialize, Deserializer, Serialize, Serializer,
+    };
+
+    use super::{Auction, Call, Doubling};
+    use crate::common::hands::{PlayerId, TrickTakingGame, MAX_PLAYERS};
+
+    impl<G> Serialize for Auction<G>
+    where
+        G: TrickTakingGame,
+        G::Bid: Serialize,
+    {
+        fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+            let mut state = serializer.serialize_struct("Auction", 8)?;
+            state.serialize_field("players", &self.dealer.players())?;
+            state.serialize_field("dealer", &self.dealer)?;
+            state.serialize_field("next_to_call", &self.next_to_call)?;
+            state.serialize_field("calls", &self.calls)?;
+            state.serialize_field("highest", &self.highest)?;
+            state.serialize_field("doubling", &self.doubling)?;
+            state.serialize_field("passed", &self.passed)?;
+            state.serialize_field("passes", &self.passes)?;
+            state.end()
+        }
+    }
+
+    #[derive(Deserialize)]
+    #[serde(rename = "Auction")]
+    struct AuctionRepr<B> {
+        players: usize,
+        dealer: usize,
+        next_to_call: Option<usize>,
+        calls: Vec<(usize, Call<B>)>,
+        highest: Option<(usize, B)>,
+        doubling: Doubling,
+        passed: [bool; MAX_PLAYERS],
+        passes: usize,
+    }
+
+    impl<'de, G> Deserialize<'de> for Auction<G>
+    where
+        G: TrickTakingGame,
+        G::Bid: Deserialize<'de>,
+    {
+        fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+            let AuctionRepr {
+                players,
+                dealer,
+                next_to_call,
+                calls,
+                highest,
+                doubling,
+                passed,
+                passes,
+            } = AuctionRepr::<G::Bid>::deserialize(deserializer)?;
+            let id = |value: usize| {
+                PlayerId::with_players(value, players).ok_or_else(|| {
+                    D::Error::custom(format!(
+                        "invalid player id {} for a game with {} players",
+                        value, players
+                    ))
+                })
+            };
+
+            Ok(Auction {
+                dealer: id(dealer)?,
+                next_to_call: next_to_call.map(id).transpose()?,
+                calls: calls
+                    .into_iter()
+                    .map(|(player, call)| Ok((id(player)?, call)))
+                    .collect::<Result<_, D::Error>>()?,
+                highest: highest
+                    .map(|(bidder, bid)| Ok::<_, D::Error>((id(bidder)?, bid)))
+                    .transpose()?,
+                doubling,
+                passed,
+                passes,
+            })
+        }
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use crate::{
+        common::{
+            cards::FrenchCard,
+            hands::{HandContext, PlayerId, TrickTakingGame},
+            teams::Teams,
+        },
+        error::ShuftError,
+    };
+
+    use super::{Auction, AuctionRules, Call, CallError, Doubling};
+
+    /// A bridge-like auction, with bids made of a level and a strain.
+    #[derive(Debug, Clone, Copy)]
+    struct Bridge;
+
+    impl TrickTakingGame for Bridge {
+        type CardType = FrenchCard;
+        type Contract = (usize, (u8, u8), Doubling);
+        type Bid = (u8, u8);
+
+        const PLAYERS: usize = 4;
+
+        const TRICKS: usize = 13;
+
+        fn teams(_players: usize) -> Teams {
+            Teams::Partnerships(2)
+        }
+
+        fn determine_taker(
+            _cards: &[FrenchCard],
+            first_to_play: PlayerId<Self>,
+            _context: &HandContext<Self>,
+        ) -> PlayerId<Self> {
+            first_to_play
+        }
+    }
+
+    impl AuctionRules for Bridge {
+        const DOUBLES: bool = true;
+
+        fn is_sufficient(bid: &(u8, u8), highest: Option<&(u8, u8)>) -> bool {
+            highest.is_none_or(|highest| bid > highest)
+        }
+
+        /// The declarer is the first player of the winning side who named
+        /// the strain of the contract.
+        fn contract(
+            bidder: PlayerId<Self>,
+            bid: (u8, u8),
+            doubling: Doubling,
+            calls: &[(PlayerId<Self>, Call<(u8, u8)>)],
+        ) -> Self::Contract {
+            let teams = Self::teams(bidder.players());
+            let declarer = calls
+                .iter()
+                .find_map(|(player, call)| match call {
+                    Call::Bid((_, strain))
+                        if *strain == bid.1 && teams.are_partners(**player, *bidder) =>
+                    {
+                        Some(*player)
+                    }
+                    _ => None,
+                })
+                .unwrap_or(bidder);
+            (*declarer, bid, doubling)
+        }
+    }
+
+    /// A skat-like auction, where the players who pass are out.
+    #[derive(Debug, Clone, Copy)]
+    struct Skat;
+
+    impl TrickTakingGame for Skat {
+        type CardType = FrenchCard;
+        type Contract = (usize, u16);
+        type Bid = u16;
+
+        const PLAYERS: usize = 3;
+
+        const TRICKS: usize = 10;
+
+        fn determine_taker(
+            _cards: &[FrenchCard],
+            first_to_play: PlayerId<Self>,
+            _context: &HandContext<Self>,
+        ) -> PlayerId<Self> {
+            first_to_play
+        }
+    }
+
+    impl AuctionRules for Skat {
+        const PASS_IS_FINAL: bool = true;
+
+        fn is_sufficient(bid: &u16, highest: Option<&u16>) -> bool {
+            highest.map_or(*bid >= 18, |highest| bid > highest)
+        }
+
+        fn contract(
+            bidder: PlayerId<Self>,
+            bid: u16,
+            _doubling: Doubling,
+            _calls: &[(PlayerId<Self>, Call<u16>)],
+        ) -> Self::Contract {
+            (*bidder, bid)
+        }
+    }
+
+    fn id<G: TrickTakingGame>(value: usize) -> PlayerId<G> {
+        PlayerId::new(value).unwrap()
+    }
+
+    #[test]
+    fn bridge_auction_with_doubles() {
+        let mut auction = Auction::<Bridge>::new(id(3));
+        assert_eq!(auction.next_to_call(), Some(id(0)));
+        assert_eq!(
+            auction.call(id(1), Call::Pass),
+            Err(ShuftError::IllegalCall(CallError::WrongTurn {
+                expected: 0,
+                found: 1
+            }))
+        );
+        assert!(!auction.is_legal(&Call::Double));
+
+        auction.call(id(0), Call::Bid((1, 0))).unwrap();
+        auction.call(id(1), Call::Double).unwrap();
+        // The partner of the bidder can redouble, but not double again.
+        assert!(auction.is_legal(&Call::Redouble));
+        assert!(!auction.is_legal(&Call::Double));
+        auction.call(id(2), Call::Bid((1, 2))).unwrap();
+        assert_eq!(auction.doubling(), Doubling::Undoubled);
+        assert_eq!(
+            auction.call(id(3), Call::Bid((1, 1))),
+            Err(ShuftError::IllegalCall(CallError::InsufficientBid))
+        );
+        assert_eq!(
+            auction.call(id(3), Call::Redouble),
+            Err(ShuftError::IllegalCall(CallError::CannotRedouble))
+        );
+        auction.call(id(3), Call::Pass).unwrap();
+        auction.call(id(0), Call::Pass).unwrap();
+        auction.call(id(1), Call::Double).unwrap();
+        auction.call(id(2), Call::Redouble).unwrap();
+        for seat in [3, 0] {
+            auction.call(id(seat), Call::Pass).unwrap();
+        }
+        assert!(!auction.is_complete());
+        assert_eq!(auction.clone().finish(), Err(ShuftError::IncompleteAuction));
+        auction.call(id(1), Call::Pass).unwrap();
+
+        assert!(auction.is_complete());
+        assert_eq!(auction.calls().len(), 10);
+        assert_eq!(
+            auction.clone().call(id(2), Call::Pass),
+            Err(ShuftError::IllegalCall(CallError::AuctionComplete))
+        );
+        assert_eq!(auction.finish(), Ok(Some((2, (1, 2), Doubling::Redoubled))));
+    }
+
+    #[test]
+    fn the_declarer_named_the_strain_first() {
+        let mut auction = Auction::<Bridge>::new(id(3));
+        let calls = [
+            Call::Bid((1, 2)),
+            Call::Pass,
+            Call::Bid((2, 2)),
+            Call::Pass,
+            Call::Pass,
+            Call::Pass,
+        ];
+        for call in calls {
+            auction.call(auction.next_to_call().unwrap(), call).unwrap();
+        }
+
+        // Player 2 made the highest bid, but player 0 named the strain first.
+        assert_eq!(auction.highest_bid(), Some((id(2), (2, 2))));
+        assert_eq!(auction.finish(), Ok(Some((0, (2, 2), Doubling::Undoubled))));
+    }
+
+    #[test]
+    fn every_player_can_pass() {
+        let mut auction = Auction::<Bridge>::new(id(0));
+        for seat in [1, 2, 3, 0] {
+            auction.call(id(seat), Call::Pass).unwrap();
+        }
+        assert_eq!(auction.finish(), Ok(None));
+    }
+
+    #[test]
+    fn players_who_pass_are_out() {
+        let mut auction = Auction::<Skat>::new(id(0));
+        auction.call(id(1), Call::Bid(18)).unwrap();
+        auction.call(id(2), Call::Bid(20)).unwrap();
+        auction.call(id(0), Call::Pass).unwrap();
+        auction.call(id(1), Call::Bid(22)).unwrap();
+        // Player 0 passed, so it's the turn of player 2 again.
+        assert_eq!(auction.next_to_call(), Some(id(2)));
+        assert_eq!(
+            auction.call(id(2), Call::Double),
+            Err(ShuftError::IllegalCall(CallError::CannotDouble))
+        );
+        auction.call(id(2), Call::Pass).unwrap();
+
+        assert_eq!(auction.highest_bid(), Some((id(1), 22)));
+        assert_eq!(auction.finish(), Ok(Some((1, 22))));
+    }
+}
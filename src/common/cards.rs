@@ -1,13 +1,154 @@
 use std::{
+    error::Error,
     fmt::{Debug, Display},
-    ops::{Deref, DerefMut},
+    hash::{Hash, Hasher},
+    iter::FusedIterator,
+    marker::PhantomData,
+    ops::{BitAnd, BitOr, Deref, DerefMut, Sub},
+    str::FromStr,
 };
 
-use rand::Rng;
+use rand::{Rng, SeedableRng};
+use rand_chacha::ChaCha8Rng;
 use strum::{EnumIter, FromRepr, IntoEnumIterator};
 
+use super::shuffle::{random_index, FisherYates, Shuffler};
+
+mod regional;
+
+pub use regional::{GermanCard, GermanRank, SpanishCard, SpanishRank, SwissCard, SwissRank};
+
+/// Implements `Serialize` and `Deserialize` for the given types through their
+/// `Display` and `FromStr` implementations, so that cards are stored with the
+/// same compact notation used to print them.
+#[cfg(feature = "serde")]
+macro_rules! serde_via_str {
+    ($($t:ty),* $(,)?) => {
+        $(
+            impl serde::Serialize for $t {
+                fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+                    serializer.collect_str(self)
+                }
+            }
+
+            impl<'de> serde::Deserialize<'de> for $t {
+                fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+                    let s = String::deserialize(deserializer)?;
+                    s.parse().map_err(serde::de::Error::custom)
+                }
+            }
+        )*
+    };
+}
+
+#[cfg(feature = "serde")]
+pub(crate) use serde_via_str;
+
+#[cfg(feature = "serde")]
+serde_via_str!(ItalianCard, FrenchCard, Joker, FrenchWithJoker, Suit);
+
+/// Implements `Ord` and `PartialOrd` for the given card types by comparing
+/// their `Card::index`, so that cards are sorted suit by suit, in the order
+/// they have in a new deck. Since the index is unique for every card, the
+/// order is consistent with `Eq`.
+macro_rules! ord_by_index {
+    ($($t:ty),* $(,)?) => {
+        $(
+            impl PartialOrd for $t {
+                fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+                    Some(self.cmp(other))
+                }
+            }
+
+            impl Ord for $t {
+                fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+                    self.index().cmp(&other.index())
+                }
+            }
+        )*
+    };
+}
+
+pub(crate) use ord_by_index;
+
+ord_by_index!(ItalianCard, FrenchCard, FrenchWithJoker);
+
 /// A trait representing a card. The actual implementation depends on the game where this is used.
-pub trait Card: Display + Default + Sized + Debug + Copy + Eq + PartialEq {}
+///
+/// Every distinct card of a type is mapped to a dense index in the range
+/// `0..DECK_SIZE`, which can be used to build lookup tables, one-hot encodings
+/// or sets of cards (see `CardSet`) generically over the type of card.
+///
+/// Cards are totally ordered suit by suit, in the order they have in a new
+/// deck, so that they can be sorted or stored in ordered collections. This
+/// order has nothing to do with the strength of a card in a game, which is
+/// defined by `TrickStrength`.
+pub trait Card: Display + Default + Sized + Debug + Copy + Eq + PartialEq + Ord {
+    /// The number of distinct cards of this type. It can't be greater than
+    /// 128, the number of cards a `CardSet` can hold.
+    const DECK_SIZE: usize;
+
+    /// The index of this card, in the range `0..DECK_SIZE`. For the standard
+    /// cards, it's the position of the card in a new deck.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use shuftlib::common::cards::{Card, Deck, ItalianCard, ItalianRank, Suit};
+    ///
+    /// let mut one_hot = [0u8; ItalianCard::DECK_SIZE];
+    /// for card in Deck::italian().iter().take(3) {
+    ///     one_hot[card.index()] = 1;
+    /// }
+    /// assert_eq!(one_hot.iter().sum::<u8>(), 3);
+    ///
+    /// let ace = ItalianCard::new(ItalianRank::Ace, Suit::Hearts);
+    /// assert_eq!(ItalianCard::from_index(0), Some(ace));
+    /// ```
+    fn index(&self) -> usize;
+
+    /// The card with the given index, or None if `index` isn't in the range
+    /// `0..DECK_SIZE`.
+    fn from_index(index: usize) -> Option<Self>;
+}
+
+/// A card that always belongs to one of the 4 suits.
+pub trait Suited: Card {
+    /// The bitmasks of the cards of each suit, in the order of `Suit`, used
+    /// by `CardSet::suit_mask`. By default, cards are assumed to be indexed
+    /// suit by suit like the standard cards, with `DECK_SIZE / 4` cards for
+    /// each suit. Card types indexed differently must override it.
+    const SUIT_MASKS: [u128; 4] = suit_masks(Self::DECK_SIZE);
+
+    /// The suit of the card.
+    fn suit(&self) -> Suit;
+}
+
+/// The default `Suited::SUIT_MASKS` of a card type with `deck_size` cards.
+const fn suit_masks(deck_size: usize) -> [u128; 4] {
+    let per_suit = deck_size / 4;
+    let suit = if per_suit == 0 {
+        0
+    } else {
+        u128::MAX >> (u128::BITS as usize - per_suit)
+    };
+
+    [
+        suit,
+        suit << per_suit,
+        suit << (2 * per_suit),
+        suit << (3 * per_suit),
+    ]
+}
+
+/// A card that has a rank. Every type of deck has its own type of rank.
+pub trait Ranked: Card {
+    /// The type of the ranks of this card.
+    type Rank: Copy + Eq + Debug;
+
+    /// The rank of the card.
+    fn rank(&self) -> Self::Rank;
+}
 
 /// Representation of a card that goes into an Italian deck.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
@@ -48,7 +189,57 @@ impl Display for ItalianCard {
     }
 }
 
-impl Card for ItalianCard {}
+impl FromStr for ItalianCard {
+    type Err = ParseCardError;
+
+    /// Parses the notation produced by `Display` ("1H", "10S"), as well as
+    /// letters for the ace and the figures ("AH", "JD", "NC", "KS") and
+    /// the initials of the Italian suits ("1B" for the ace of batons).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use shuftlib::common::cards::{ItalianCard, ItalianRank, Suit};
+    ///
+    /// let card = ItalianCard::new(ItalianRank::King, Suit::Spades);
+    /// assert_eq!("10S".parse::<ItalianCard>(), Ok(card));
+    /// assert_eq!("KS".parse::<ItalianCard>(), Ok(card));
+    /// assert_eq!(card.to_string().parse::<ItalianCard>(), Ok(card));
+    /// ```
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (rank, suit) = split_card(s)?;
+
+        Ok(ItalianCard::new(rank.parse()?, suit.parse()?))
+    }
+}
+
+impl Card for ItalianCard {
+    const DECK_SIZE: usize = ITALIAN_CARDS;
+
+    fn index(&self) -> usize {
+        self.suit as usize * ITALIAN_RANKS + self.rank as usize - 1
+    }
+
+    fn from_index(index: usize) -> Option<Self> {
+        let suit = Suit::from_repr(index / ITALIAN_RANKS)?;
+        let rank = ItalianRank::from_repr((index % ITALIAN_RANKS + 1) as u8)?;
+        Some(ItalianCard::new(rank, suit))
+    }
+}
+
+impl Suited for ItalianCard {
+    fn suit(&self) -> Suit {
+        self.suit
+    }
+}
+
+impl Ranked for ItalianCard {
+    type Rank = ItalianRank;
+
+    fn rank(&self) -> ItalianRank {
+        self.rank
+    }
+}
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 /// Representation of a card that goes into an French deck.
@@ -89,17 +280,105 @@ impl Display for FrenchCard {
     }
 }
 
-impl Card for FrenchCard {}
+impl FromStr for FrenchCard {
+    type Err = ParseCardError;
+
+    /// Parses the notation produced by `Display` ("1H", "12D"), as well as
+    /// the usual letters for the ace, the ten and the figures ("AH", "TD",
+    /// "QC", "KS").
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use shuftlib::common::cards::{FrenchCard, FrenchRank, Suit};
+    ///
+    /// let card = FrenchCard::new(FrenchRank::Ten, Suit::Diamonds);
+    /// assert_eq!("10D".parse::<FrenchCard>(), Ok(card));
+    /// assert_eq!("TD".parse::<FrenchCard>(), Ok(card));
+    /// ```
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (rank, suit) = split_card(s)?;
+
+        Ok(FrenchCard::new(rank.parse()?, suit.parse()?))
+    }
+}
+
+impl Card for FrenchCard {
+    const DECK_SIZE: usize = FRENCH_CARDS;
+
+    fn index(&self) -> usize {
+        self.suit as usize * FRENCH_RANKS + self.rank as usize - 1
+    }
+
+    fn from_index(index: usize) -> Option<Self> {
+        let suit = Suit::from_repr(index / FRENCH_RANKS)?;
+        let rank = FrenchRank::from_repr((index % FRENCH_RANKS + 1) as u8)?;
+        Some(FrenchCard::new(rank, suit))
+    }
+}
+
+impl Suited for FrenchCard {
+    fn suit(&self) -> Suit {
+        self.suit
+    }
+}
+
+impl Ranked for FrenchCard {
+    type Rank = FrenchRank;
+
+    fn rank(&self) -> FrenchRank {
+        self.rank
+    }
+}
 
 /// A Joker card, present in some card games. Its function depends on the game.
-#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
-pub struct Joker;
+/// A French deck has up to two jokers, told apart by their color.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, EnumIter, FromRepr, Hash)]
+pub enum Joker {
+    /// The red joker, the first one of the deck.
+    #[default]
+    Red,
+    /// The black joker.
+    Black,
+}
+
+impl Card for Joker {
+    const DECK_SIZE: usize = 2;
 
-impl Card for Joker {}
+    fn index(&self) -> usize {
+        *self as usize
+    }
+
+    fn from_index(index: usize) -> Option<Self> {
+        Joker::from_repr(index)
+    }
+}
 
 impl Display for Joker {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(f, "JK")
+        match self {
+            Joker::Red => write!(f, "JK"),
+            Joker::Black => write!(f, "JB"),
+        }
+    }
+}
+
+impl FromStr for Joker {
+    type Err = ParseCardError;
+
+    /// Accepts "JK" or "joker" for the red joker, and "JB" for the black
+    /// one, ignoring case.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim();
+        if s.eq_ignore_ascii_case("JK") || s.eq_ignore_ascii_case("joker") {
+            Ok(Joker::Red)
+        } else if s.eq_ignore_ascii_case("JB") {
+            Ok(Joker::Black)
+        } else if s.is_empty() {
+            Err(ParseCardError::Empty)
+        } else {
+            Err(ParseCardError::InvalidJoker(s.to_string()))
+        }
     }
 }
 
@@ -111,7 +390,24 @@ pub enum FrenchWithJoker {
     /// A Joker card.
     Joker(Joker),
 }
-impl Card for FrenchWithJoker {}
+/// The jokers follow the 52 French cards, the red one first.
+impl Card for FrenchWithJoker {
+    const DECK_SIZE: usize = FRENCH_CARDS + Joker::DECK_SIZE;
+
+    fn index(&self) -> usize {
+        match self {
+            FrenchWithJoker::Normal(c) => c.index(),
+            FrenchWithJoker::Joker(j) => FRENCH_CARDS + j.index(),
+        }
+    }
+
+    fn from_index(index: usize) -> Option<Self> {
+        match index.checked_sub(FRENCH_CARDS) {
+            Some(joker) => Joker::from_index(joker).map(FrenchWithJoker::Joker),
+            None => FrenchCard::from_index(index).map(FrenchWithJoker::Normal),
+        }
+    }
+}
 
 impl Default for FrenchWithJoker {
     fn default() -> Self {
@@ -131,7 +427,21 @@ impl Display for FrenchWithJoker {
     }
 }
 
-#[derive(Debug, Clone, Copy, Eq, PartialEq, EnumIter, FromRepr, Hash)]
+impl FromStr for FrenchWithJoker {
+    type Err = ParseCardError;
+
+    /// Parses a joker if the string is a valid notation for it, otherwise
+    /// tries to parse a `FrenchCard`.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.parse::<Joker>() {
+            Ok(joker) => Ok(FrenchWithJoker::Joker(joker)),
+            Err(_) => s.parse().map(FrenchWithJoker::Normal),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Eq, PartialEq, PartialOrd, Ord, EnumIter, FromRepr, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[repr(u8)]
 /// The rank of the card. In an Italian deck, ranks go from the ace to the 7, then they also have a jack, knight and king,
 /// In most games they each have a different value that depends on the game itself.
@@ -158,7 +468,27 @@ pub enum ItalianRank {
     King,
 }
 
-#[derive(Debug, Clone, Copy, Eq, PartialEq, EnumIter, FromRepr, Hash)]
+impl FromStr for ItalianRank {
+    type Err = ParseCardError;
+
+    /// Parses the numeric value of the rank (1 to 10) or one of the letters
+    /// "A" (ace), "J" or "F" (jack, fante), "N", "Q" or "C" (knight, cavallo)
+    /// and "K" or "R" (king, re), ignoring case.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let rank = match s.to_ascii_uppercase().as_str() {
+            "A" => Some(ItalianRank::Ace),
+            "J" | "F" => Some(ItalianRank::Jack),
+            "N" | "Q" | "C" => Some(ItalianRank::Knight),
+            "K" | "R" => Some(ItalianRank::King),
+            n => n.parse().ok().and_then(ItalianRank::from_repr),
+        };
+
+        rank.ok_or_else(|| ParseCardError::InvalidRank(s.to_string()))
+    }
+}
+
+#[derive(Debug, Clone, Copy, Eq, PartialEq, PartialOrd, Ord, EnumIter, FromRepr, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[repr(u8)]
 /// The rank of the card. In a French deck, ranks go from the ace to 10, then there is a jack, queen and king,
 /// In most games they each have a different value that depends on the game itself.
@@ -191,7 +521,27 @@ pub enum FrenchRank {
     King,
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, EnumIter, Hash)]
+impl FromStr for FrenchRank {
+    type Err = ParseCardError;
+
+    /// Parses the numeric value of the rank (1 to 13) or one of the letters
+    /// "A" (ace), "T" (ten), "J" (jack), "Q" (queen) and "K" (king), ignoring
+    /// case.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let rank = match s.to_ascii_uppercase().as_str() {
+            "A" => Some(FrenchRank::Ace),
+            "T" => Some(FrenchRank::Ten),
+            "J" => Some(FrenchRank::Jack),
+            "Q" => Some(FrenchRank::Queen),
+            "K" => Some(FrenchRank::King),
+            n => n.parse().ok().and_then(FrenchRank::from_repr),
+        };
+
+        rank.ok_or_else(|| ParseCardError::InvalidRank(s.to_string()))
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, EnumIter, FromRepr, Hash)]
 /// The 4 suits of a standard deck. They have an equivalent in pretty much all regional decks.
 /// In some games they have a hierarchical order.
 pub enum Suit {
@@ -217,7 +567,102 @@ impl Display for Suit {
     }
 }
 
-#[derive(Default)]
+impl FromStr for Suit {
+    type Err = ParseCardError;
+
+    /// Parses the letters used by `Display` ("H", "D", "C", "S"), "B" for
+    /// batons, the suit symbols and the full English, Italian or Spanish names
+    /// of the suits, ignoring case. The letters of `Display` take precedence
+    /// over the Italian initials: "C" is always clubs (bastoni), never coppe,
+    /// while "D" happens to be denari in both. Coppe can only be parsed by
+    /// name, or as "H".
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use shuftlib::common::cards::Suit;
+    ///
+    /// assert_eq!("H".parse::<Suit>(), Ok(Suit::Hearts));
+    /// assert_eq!("coppe".parse::<Suit>(), Ok(Suit::Hearts));
+    /// assert_eq!("C".parse::<Suit>(), Ok(Suit::Clubs));
+    /// assert_eq!("B".parse::<Suit>(), Ok(Suit::Clubs));
+    /// assert_eq!("♠".parse::<Suit>(), Ok(Suit::Spades));
+    /// ```
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let suit = match s.to_lowercase().as_str() {
+            "h" | "♥" | "♡" | "hearts" | "cups" | "coppe" | "copas" | "roses" => {
+                Some(Suit::Hearts)
+            }
+            "d" | "♦" | "♢" | "diamonds" | "tiles" | "coins" | "denari" | "oros" | "bells" => {
+                Some(Suit::Diamonds)
+            }
+            "c" | "b" | "♣" | "♧" | "clubs" | "clovers" | "batons" | "bastoni" | "bastos"
+            | "acorns" => Some(Suit::Clubs),
+            "s" | "♠" | "♤" | "spades" | "pikes" | "swords" | "spade" | "espadas" | "leaves"
+            | "shields" => Some(Suit::Spades),
+            _ => None,
+        };
+
+        suit.ok_or_else(|| ParseCardError::InvalidSuit(s.to_string()))
+    }
+}
+
+/// The error returned when parsing a card, a rank or a suit from a string
+/// fails.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ParseCardError {
+    /// The string was empty.
+    Empty,
+    /// The rank part of the string isn't a valid rank for the card type.
+    InvalidRank(String),
+    /// The suit part of the string isn't a valid suit.
+    InvalidSuit(String),
+    /// The string was expected to be a joker, but it isn't.
+    InvalidJoker(String),
+}
+
+impl Display for ParseCardError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ParseCardError::Empty => write!(f, "cannot parse a card from an empty string"),
+            ParseCardError::InvalidRank(r) => write!(f, "invalid rank: {:?}", r),
+            ParseCardError::InvalidSuit(s) => write!(f, "invalid suit: {:?}", s),
+            ParseCardError::InvalidJoker(j) => write!(f, "invalid joker: {:?}", j),
+        }
+    }
+}
+
+impl Error for ParseCardError {}
+
+/// Splits the notation of a card into its rank and suit parts. The rank is
+/// either a sequence of digits or a single character, the suit is whatever
+/// follows it.
+fn split_card(s: &str) -> Result<(&str, &str), ParseCardError> {
+    let s = s.trim();
+    if s.is_empty() {
+        return Err(ParseCardError::Empty);
+    }
+
+    let rank_len = match s.find(|c: char| !c.is_ascii_digit()) {
+        Some(0) => s.chars().next().map_or(0, char::len_utf8),
+        Some(len) => len,
+        None => s.len(),
+    };
+
+    let (rank, suit) = s.split_at(rank_len);
+    if suit.is_empty() {
+        return Err(ParseCardError::InvalidSuit(suit.to_string()));
+    }
+
+    Ok((rank, suit))
+}
+
+#[derive(Default, Clone, Debug)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(transparent)
+)]
 /// Represents a deck of cards. Cards can be added or removed at will.
 pub struct Deck<T>
 where
@@ -228,6 +673,25 @@ where
 
 const FRENCH_CARDS: usize = 52;
 const ITALIAN_CARDS: usize = 40;
+const FRENCH_RANKS: usize = 13;
+/// The French ranks from the lowest to the highest, when the ace is high.
+/// Stripped decks are made of the highest ranks.
+const FRENCH_ACE_HIGH: [FrenchRank; FRENCH_RANKS] = [
+    FrenchRank::Two,
+    FrenchRank::Three,
+    FrenchRank::Four,
+    FrenchRank::Five,
+    FrenchRank::Six,
+    FrenchRank::Seven,
+    FrenchRank::Eight,
+    FrenchRank::Nine,
+    FrenchRank::Ten,
+    FrenchRank::Jack,
+    FrenchRank::Queen,
+    FrenchRank::King,
+    FrenchRank::Ace,
+];
+const ITALIAN_RANKS: usize = 10;
 
 impl Deck<ItalianCard> {
     /// Creates a new deck in the Italian format.
@@ -241,6 +705,22 @@ impl Deck<ItalianCard> {
 
         Deck { cards }
     }
+
+    /// Creates a new deck in the Italian format, shuffled with the given seed.
+    /// See `Deck::shuffle_seeded` for the reproducibility guarantees.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use shuftlib::common::cards::Deck;
+    ///
+    /// assert_eq!(*Deck::italian_shuffled(42), *Deck::italian_shuffled(42));
+    /// ```
+    pub fn italian_shuffled(seed: u64) -> Deck<ItalianCard> {
+        let mut deck = Deck::italian();
+        deck.shuffle_seeded(seed);
+        deck
+    }
 }
 
 impl Deck<FrenchCard> {
@@ -256,7 +736,40 @@ impl Deck<FrenchCard> {
         Deck { cards }
     }
 
-    /// Creates a new 52 cards French deck, with the addition of the specified amount of jokers.
+    /// Creates a new 32 cards French deck, with ranks from 7 to ace, as used in
+    /// piquet and belote.
+    pub fn piquet() -> Deck<FrenchCard> {
+        DeckBuilder::new(Deck::french())
+            .ranks(&FRENCH_ACE_HIGH[5..])
+            .build()
+    }
+
+    /// Creates a new 24 cards French deck, with ranks from 9 to ace, as used in
+    /// euchre.
+    pub fn euchre() -> Deck<FrenchCard> {
+        DeckBuilder::new(Deck::french())
+            .ranks(&FRENCH_ACE_HIGH[7..])
+            .build()
+    }
+
+    /// Creates a new 36 cards French deck, with ranks from 6 to ace, as used in
+    /// durak.
+    pub fn durak() -> Deck<FrenchCard> {
+        DeckBuilder::new(Deck::french())
+            .ranks(&FRENCH_ACE_HIGH[4..])
+            .build()
+    }
+
+    /// Creates a new 52 cards French deck, shuffled with the given seed. See
+    /// `Deck::shuffle_seeded` for the reproducibility guarantees.
+    pub fn french_shuffled(seed: u64) -> Deck<FrenchCard> {
+        let mut deck = Deck::french();
+        deck.shuffle_seeded(seed);
+        deck
+    }
+
+    /// Creates a new 52 cards French deck, with the addition of the specified
+    /// amount of jokers, at most 2: the red one, then the black one.
     pub fn french_with_jokers(jokers: u8) -> Deck<FrenchWithJoker> {
         let mut cards = Vec::with_capacity(FRENCH_CARDS + jokers as usize);
         for suit in Suit::iter() {
@@ -265,32 +778,87 @@ impl Deck<FrenchCard> {
             }
         }
 
-        for _ in 0..jokers {
-            cards.push(FrenchWithJoker::Joker(Joker {}));
-        }
+        cards.extend(
+            Joker::iter()
+                .take(jokers as usize)
+                .map(FrenchWithJoker::Joker),
+        );
 
         Deck { cards }
     }
 }
 
 impl<T: Card> Deck<T> {
-    /// Performs a random permutation on the deck with the Fisher–Yates shuffle algorithm, repeated 10 times.
+    /// Performs a uniformly random permutation of the deck with the
+    /// Fisher–Yates shuffle algorithm, using the thread-local random number
+    /// generator.
     pub fn shuffle(&mut self) {
-        let mut rng = rand::thread_rng();
-        let max = self.cards.len();
-        for _ in 0..10 {
-            for i in 0..max - 2 {
-                let j = rng.gen_range(i..max);
-                self.cards.swap(i, j);
-            }
-        }
+        self.shuffle_with(&mut rand::thread_rng());
     }
 
-    /// Adds a card in a random position inside the deck.
+    /// Performs a uniformly random permutation of the deck with the
+    /// Fisher–Yates shuffle algorithm, drawing random numbers from `rng`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rand::SeedableRng;
+    /// use rand_chacha::ChaCha8Rng;
+    /// use shuftlib::common::cards::Deck;
+    ///
+    /// let mut first = Deck::italian();
+    /// first.shuffle_with(&mut ChaCha8Rng::seed_from_u64(7));
+    /// let mut second = Deck::italian();
+    /// second.shuffle_with(&mut ChaCha8Rng::seed_from_u64(7));
+    ///
+    /// assert_eq!(*first, *second);
+    /// ```
+    pub fn shuffle_with<R: Rng + ?Sized>(&mut self, rng: &mut R) {
+        self.shuffle_by(&FisherYates, rng);
+    }
+
+    /// Shuffles the deck with the given algorithm, drawing random numbers from
+    /// `rng`. See the `shuffle` module for the available algorithms.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rand::SeedableRng;
+    /// use rand_chacha::ChaCha8Rng;
+    /// use shuftlib::common::{cards::Deck, shuffle::{Cut, Riffle}};
+    ///
+    /// let mut rng = ChaCha8Rng::seed_from_u64(7);
+    /// let mut deck = Deck::french();
+    /// deck.shuffle_by(&Riffle::default(), &mut rng);
+    /// deck.shuffle_by(&Cut, &mut rng);
+    ///
+    /// assert_eq!(deck.len(), 52);
+    /// ```
+    pub fn shuffle_by<S: Shuffler, R: Rng + ?Sized>(&mut self, shuffler: &S, rng: &mut R) {
+        shuffler.shuffle(&mut self.cards, rng);
+    }
+
+    /// Shuffles the deck with a ChaCha8 generator initialized from `seed`.
+    ///
+    /// The permutation produced by a seed is part of the public API of this
+    /// crate: shuffling the same deck with the same seed gives the same
+    /// result on every platform, and changing it is considered a breaking
+    /// change.
+    pub fn shuffle_seeded(&mut self, seed: u64) {
+        self.shuffle_with(&mut ChaCha8Rng::seed_from_u64(seed));
+    }
+
+    /// Adds a card in a random position inside the deck, using the
+    /// thread-local random number generator.
     pub fn shuffle_card(&mut self, card: T) {
-        let mut rng = rand::thread_rng();
-        let max = self.cards.len();
-        let position = rng.gen_range(1..max);
+        self.shuffle_card_with(card, &mut rand::thread_rng());
+    }
+
+    /// Adds a card in a random position inside the deck, drawing random
+    /// numbers from `rng`. Every position, including the top and the bottom
+    /// of the deck, has the same probability.
+    pub fn shuffle_card_with<R: Rng + ?Sized>(&mut self, card: T, rng: &mut R) {
+        let position = random_index(rng, self.cards.len() + 1);
         self.cards.insert(position, card);
     }
 
@@ -332,6 +900,77 @@ impl<T: Card> Deck<T> {
     }
 }
 
+/// Builds a deck starting from another one, by removing some of its cards and
+/// repeating the remaining ones a number of times.
+///
+/// # Examples
+///
+/// ```
+/// use shuftlib::common::cards::{Deck, DeckBuilder, FrenchRank};
+///
+/// // The 48 cards pinochle deck: two copies of the cards from 9 to ace.
+/// let pinochle = DeckBuilder::new(Deck::french())
+///     .ranks(&[
+///         FrenchRank::Nine,
+///         FrenchRank::Ten,
+///         FrenchRank::Jack,
+///         FrenchRank::Queen,
+///         FrenchRank::King,
+///         FrenchRank::Ace,
+///     ])
+///     .copies(2)
+///     .build();
+///
+/// assert_eq!(pinochle.len(), 48);
+/// ```
+#[derive(Debug, Clone)]
+pub struct DeckBuilder<T: Card> {
+    cards: Vec<T>,
+    copies: usize,
+}
+
+impl<T: Card> DeckBuilder<T> {
+    /// Starts building a deck from the cards of `deck`.
+    pub fn new(deck: Deck<T>) -> Self {
+        Self {
+            cards: deck.cards,
+            copies: 1,
+        }
+    }
+
+    /// Only keeps the cards for which `predicate` returns true.
+    pub fn filter<F: FnMut(&T) -> bool>(mut self, predicate: F) -> Self {
+        self.cards.retain(predicate);
+        self
+    }
+
+    /// Sets how many times the cards are repeated in the resulting deck. The
+    /// copies are placed one after the other.
+    pub fn copies(mut self, copies: usize) -> Self {
+        self.copies = copies;
+        self
+    }
+
+    /// Creates the deck.
+    pub fn build(self) -> Deck<T> {
+        Deck::from_vec(self.cards.repeat(self.copies))
+    }
+}
+
+impl<T: Ranked> DeckBuilder<T> {
+    /// Only keeps the cards with one of the given ranks.
+    pub fn ranks(self, ranks: &[T::Rank]) -> Self {
+        self.filter(|c| ranks.contains(&c.rank()))
+    }
+}
+
+impl<T: Suited> DeckBuilder<T> {
+    /// Only keeps the cards with one of the given suits.
+    pub fn suits(self, suits: &[Suit]) -> Self {
+        self.filter(|c| suits.contains(&c.suit()))
+    }
+}
+
 impl<T> Deref for Deck<T>
 where
     T: Card,
@@ -352,9 +991,308 @@ where
     }
 }
 
+/// A set of distinct cards, backed by a bitmask. Adding, removing and looking
+/// up cards, as well as the usual set operations, take constant time.
+/// Every card is represented by the bit at position `Card::index`, so
+/// iteration follows the order of the indices, which for the standard cards is
+/// the same order of the cards in a new deck.
+///
+/// # Examples
+///
+/// ```
+/// use shuftlib::common::cards::{CardSet, ItalianCard, ItalianRank, Suit};
+///
+/// let ace = ItalianCard::new(ItalianRank::Ace, Suit::Spades);
+/// let two = ItalianCard::new(ItalianRank::Two, Suit::Hearts);
+/// let mut set = CardSet::new();
+/// assert!(set.insert(ace));
+/// assert!(set.insert(two));
+/// assert!(!set.insert(ace));
+///
+/// assert_eq!(set.len(), 2);
+/// assert_eq!(set.of_suit(Suit::Spades).iter().collect::<Vec<_>>(), [ace]);
+/// assert_eq!(set.iter().collect::<Vec<_>>(), [two, ace]);
+/// ```
+pub struct CardSet<T: Card> {
+    bits: u128,
+    card: PhantomData<T>,
+}
+
+impl<T: Card> CardSet<T> {
+    /// The bits representing every card of type `T`. Card types with more
+    /// than 128 cards don't fit in a set, and using them fails to compile.
+    const MASK: u128 = {
+        assert!(
+            T::DECK_SIZE <= u128::BITS as usize,
+            "a CardSet can't hold more than 128 distinct cards"
+        );
+        if T::DECK_SIZE == 0 {
+            0
+        } else {
+            u128::MAX >> (u128::BITS as usize - T::DECK_SIZE)
+        }
+    };
+
+    /// Creates an empty set.
+    pub fn new() -> Self {
+        Self::from_bits(0)
+    }
+
+    /// Creates a set containing every distinct card of type `T`.
+    pub fn full() -> Self {
+        Self::from_bits(Self::MASK)
+    }
+
+    /// Creates a set from its bitmask. Bits that don't represent any card
+    /// are ignored.
+    pub fn from_bits(bits: u128) -> Self {
+        Self {
+            bits: bits & Self::MASK,
+            card: PhantomData,
+        }
+    }
+
+    /// The bitmask representing this set. See `Card::index`.
+    pub fn bits(&self) -> u128 {
+        self.bits
+    }
+
+    /// Adds a card to the set. Returns whether the card was not already in
+    /// the set.
+    pub fn insert(&mut self, card: T) -> bool {
+        let bit = 1 << card.index();
+        let inserted = self.bits & bit == 0;
+        self.bits |= bit;
+        inserted
+    }
+
+    /// Removes a card from the set. Returns whether the card was in the set.
+    pub fn remove(&mut self, card: T) -> bool {
+        let bit = 1 << card.index();
+        let removed = self.bits & bit != 0;
+        self.bits &= !bit;
+        removed
+    }
+
+    /// Returns whether the set contains the given card.
+    pub fn contains(&self, card: T) -> bool {
+        self.bits & (1 << card.index()) != 0
+    }
+
+    /// The number of cards in the set.
+    pub fn len(&self) -> usize {
+        self.bits.count_ones() as usize
+    }
+
+    /// Returns whether the set contains no cards.
+    pub fn is_empty(&self) -> bool {
+        self.bits == 0
+    }
+
+    /// Returns the cards contained in `self` or in `other`.
+    pub fn union(&self, other: &Self) -> Self {
+        Self::from_bits(self.bits | other.bits)
+    }
+
+    /// Returns the cards contained both in `self` and in `other`.
+    pub fn intersection(&self, other: &Self) -> Self {
+        Self::from_bits(self.bits & other.bits)
+    }
+
+    /// Returns the cards contained in `self` but not in `other`.
+    pub fn difference(&self, other: &Self) -> Self {
+        Self::from_bits(self.bits & !other.bits)
+    }
+
+    /// Returns whether every card of `self` is also contained in `other`.
+    pub fn is_subset(&self, other: &Self) -> bool {
+        self.bits & !other.bits == 0
+    }
+
+    /// Iterates over the cards of the set, in the order of their indices.
+    pub fn iter(&self) -> CardSetIter<T> {
+        CardSetIter {
+            bits: self.bits,
+            card: PhantomData,
+        }
+    }
+}
+
+impl<T: Suited> CardSet<T> {
+    /// Creates a set containing every card of the given suit, see
+    /// `Suited::SUIT_MASKS`.
+    pub fn suit_mask(suit: Suit) -> Self {
+        Self::from_bits(T::SUIT_MASKS[suit as usize])
+    }
+
+    /// Returns the cards of this set that belong to the given suit.
+    pub fn of_suit(&self, suit: Suit) -> Self {
+        self.intersection(&Self::suit_mask(suit))
+    }
+}
+
+impl<T: Card> Default for CardSet<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: Card> Clone for CardSet<T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T: Card> Copy for CardSet<T> {}
+
+impl<T: Card> PartialEq for CardSet<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.bits == other.bits
+    }
+}
+
+impl<T: Card> Eq for CardSet<T> {}
+
+impl<T: Card> Hash for CardSet<T> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.bits.hash(state);
+    }
+}
+
+impl<T: Card> Debug for CardSet<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_set().entries(self.iter()).finish()
+    }
+}
+
+impl<T: Card> BitOr for CardSet<T> {
+    type Output = Self;
+
+    fn bitor(self, rhs: Self) -> Self::Output {
+        self.union(&rhs)
+    }
+}
+
+impl<T: Card> BitAnd for CardSet<T> {
+    type Output = Self;
+
+    fn bitand(self, rhs: Self) -> Self::Output {
+        self.intersection(&rhs)
+    }
+}
+
+impl<T: Card> Sub for CardSet<T> {
+    type Output = Self;
+
+    fn sub(self, rhs: Self) -> Self::Output {
+        self.difference(&rhs)
+    }
+}
+
+impl<T: Card> FromIterator<T> for CardSet<T> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let mut set = Self::new();
+        set.extend(iter);
+        set
+    }
+}
+
+impl<T: Card> Extend<T> for CardSet<T> {
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        iter.into_iter().for_each(|c| {
+            self.insert(c);
+        });
+    }
+}
+
+impl<T: Card> IntoIterator for CardSet<T> {
+    type Item = T;
+    type IntoIter = CardSetIter<T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl<T: Card> IntoIterator for &CardSet<T> {
+    type Item = T;
+    type IntoIter = CardSetIter<T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+/// An iterator over the cards of a `CardSet`, created by `CardSet::iter`.
+#[derive(Debug, Clone)]
+pub struct CardSetIter<T> {
+    bits: u128,
+    card: PhantomData<T>,
+}
+
+impl<T: Card> Iterator for CardSetIter<T> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while self.bits != 0 {
+            let bit = self.bits.trailing_zeros() as usize;
+            self.bits &= self.bits - 1;
+            if let Some(card) = T::from_index(bit) {
+                return Some(card);
+            }
+        }
+
+        None
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.bits.count_ones() as usize;
+        (len, Some(len))
+    }
+}
+
+impl<T: Card> ExactSizeIterator for CardSetIter<T> {}
+
+impl<T: Card> FusedIterator for CardSetIter<T> {}
+
+#[cfg(feature = "serde")]
+impl<T: Card + serde::Serialize> serde::Serialize for CardSet<T> {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_seq(self.iter())
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de, T: Card + serde::Deserialize<'de>> serde::Deserialize<'de> for CardSet<T> {
+    /// Fails if the same card appears more than once.
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let mut set = Self::new();
+        for card in Vec::<T>::deserialize(deserializer)? {
+            if !set.insert(card) {
+                return Err(serde::de::Error::custom(format!(
+                    "duplicate card {} in set",
+                    card
+                )));
+            }
+        }
+
+        Ok(set)
+    }
+}
+
 #[cfg(test)]
+#[allow(clippy::unwrap_used)]
 mod tests {
-    use crate::common::cards::Deck;
+    use std::collections::BTreeSet;
+
+    use crate::common::cards::{
+        Card, CardSet, Deck, DeckBuilder, FrenchCard, FrenchRank, FrenchWithJoker, ItalianCard,
+        ItalianRank, Joker, ParseCardError, Suit, Suited,
+    };
+    use proptest::prelude::*;
+    use rand::SeedableRng;
+    use rand_chacha::ChaCha8Rng;
+    use strum::IntoEnumIterator;
 
     #[test]
     fn should_shuffle() {
@@ -369,4 +1307,280 @@ mod tests {
 
         assert_ne!(count_of_different_cards, 0);
     }
+
+    #[test]
+    fn parse_round_trips_display() {
+        for card in Deck::italian().iter() {
+            assert_eq!(card.to_string().parse::<ItalianCard>(), Ok(*card));
+        }
+
+        for card in Deck::french().iter() {
+            assert_eq!(card.to_string().parse::<FrenchCard>(), Ok(*card));
+        }
+
+        for card in Deck::french_with_jokers(2).iter() {
+            assert_eq!(card.to_string().parse::<FrenchWithJoker>(), Ok(*card));
+        }
+
+        for suit in Suit::iter() {
+            assert_eq!(suit.to_string().parse::<Suit>(), Ok(suit));
+        }
+
+        for joker in Joker::iter() {
+            assert_eq!(joker.to_string().parse::<Joker>(), Ok(joker));
+        }
+    }
+
+    #[test]
+    fn parse_accepts_alternative_notations() {
+        let cases = [
+            ("AH", FrenchRank::Ace, Suit::Hearts),
+            ("KS", FrenchRank::King, Suit::Spades),
+            ("TD", FrenchRank::Ten, Suit::Diamonds),
+            ("qc", FrenchRank::Queen, Suit::Clubs),
+            (" 11♣ ", FrenchRank::Jack, Suit::Clubs),
+        ];
+        for (s, rank, suit) in cases {
+            assert_eq!(s.parse::<FrenchCard>(), Ok(FrenchCard::new(rank, suit)));
+        }
+
+        let cases = [
+            ("1B", ItalianRank::Ace, Suit::Clubs),
+            ("RD", ItalianRank::King, Suit::Diamonds),
+            ("Ccoppe", ItalianRank::Knight, Suit::Hearts),
+            ("7spade", ItalianRank::Seven, Suit::Spades),
+        ];
+        for (s, rank, suit) in cases {
+            assert_eq!(s.parse::<ItalianCard>(), Ok(ItalianCard::new(rank, suit)));
+        }
+
+        assert_eq!(
+            "joker".parse::<FrenchWithJoker>(),
+            Ok(FrenchWithJoker::Joker(Joker::Red))
+        );
+    }
+
+    #[test]
+    fn italian_suits_are_parsed_by_name() {
+        assert_eq!("coppe".parse::<Suit>(), Ok(Suit::Hearts));
+        assert_eq!("Bastoni".parse::<Suit>(), Ok(Suit::Clubs));
+        assert_eq!("denari".parse::<Suit>(), Ok(Suit::Diamonds));
+        assert_eq!("spade".parse::<Suit>(), Ok(Suit::Spades));
+        // The letters of `Display` take precedence: "C" is clubs, not coppe.
+        assert_eq!("C".parse::<Suit>(), Ok(Suit::Clubs));
+        assert_eq!(
+            "1C".parse::<ItalianCard>().map(|c| c.suit()),
+            Ok(Suit::Clubs)
+        );
+    }
+
+    #[test]
+    fn parse_rejects_invalid_notations() {
+        assert_eq!("".parse::<ItalianCard>(), Err(ParseCardError::Empty));
+        assert_eq!(
+            "11H".parse::<ItalianCard>(),
+            Err(ParseCardError::InvalidRank("11".to_string()))
+        );
+        assert_eq!(
+            "TH".parse::<ItalianCard>(),
+            Err(ParseCardError::InvalidRank("T".to_string()))
+        );
+        assert_eq!(
+            "1X".parse::<FrenchCard>(),
+            Err(ParseCardError::InvalidSuit("X".to_string()))
+        );
+        assert_eq!(
+            "10".parse::<FrenchCard>(),
+            Err(ParseCardError::InvalidSuit(String::new()))
+        );
+        assert_eq!(
+            "1H".parse::<Joker>(),
+            Err(ParseCardError::InvalidJoker("1H".to_string()))
+        );
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn serde_uses_card_notation() {
+        let deck = Deck::french_with_jokers(1);
+        let json = serde_json::to_string(&deck).unwrap();
+        assert!(json.starts_with(r#"["1H","2H","#));
+        assert!(json.ends_with(r#""13S","JK"]"#));
+
+        let deserialized: Deck<FrenchWithJoker> = serde_json::from_str(&json).unwrap();
+        assert_eq!(*deserialized, *deck);
+
+        assert!(serde_json::from_str::<ItalianCard>(r#""11H""#).is_err());
+    }
+
+    #[test]
+    fn seeded_shuffle_is_reproducible() {
+        assert_eq!(*Deck::italian_shuffled(1), *Deck::italian_shuffled(1));
+        assert_ne!(*Deck::italian_shuffled(1), *Deck::italian_shuffled(2));
+        assert_eq!(*Deck::french_shuffled(1), *Deck::french_shuffled(1));
+
+        let mut deck = Deck::italian_shuffled(3);
+        let mut other = deck.clone();
+        let card = ItalianCard::new(ItalianRank::Ace, Suit::Hearts);
+        deck.shuffle_card_with(card, &mut ChaCha8Rng::seed_from_u64(4));
+        other.shuffle_card_with(card, &mut ChaCha8Rng::seed_from_u64(4));
+        assert_eq!(*deck, *other);
+    }
+
+    #[test]
+    fn seeded_shuffle_is_stable() {
+        // Changing the output of a seeded shuffle is a breaking change: if
+        // this test fails, the new algorithm must be released with a new
+        // major version.
+        let deck = Deck::italian_shuffled(42);
+        let first = deck
+            .iter()
+            .take(5)
+            .map(|c| c.to_string())
+            .collect::<Vec<_>>();
+        assert_eq!(first, ["8S", "9S", "9D", "10D", "5C"]);
+    }
+
+    #[test]
+    fn small_decks_can_be_shuffled() {
+        let mut deck = Deck::<ItalianCard>::new();
+        deck.shuffle();
+        assert!(deck.is_empty());
+
+        let card = ItalianCard::new(ItalianRank::Ace, Suit::Hearts);
+        deck.shuffle_card(card);
+        deck.shuffle();
+        assert_eq!(*deck, [card]);
+    }
+
+    #[test]
+    fn shuffle_card_reaches_every_position() {
+        let card = ItalianCard::new(ItalianRank::Ace, Suit::Hearts);
+        let mut rng = ChaCha8Rng::seed_from_u64(0);
+        let mut reached = [false; 3];
+        for _ in 0..100 {
+            let mut deck = Deck::from_vec(vec![ItalianCard::default(); 2]);
+            deck.shuffle_card_with(card, &mut rng);
+            if let Some(position) = deck.iter().position(|&c| c == card) {
+                reached[position] = true;
+            }
+        }
+
+        assert_eq!(reached, [true; 3]);
+    }
+
+    #[test]
+    fn indices_follow_deck_order() {
+        for (i, card) in Deck::italian().iter().enumerate() {
+            assert_eq!(card.index(), i);
+            assert_eq!(ItalianCard::from_index(i), Some(*card));
+        }
+        assert_eq!(ItalianCard::from_index(ItalianCard::DECK_SIZE), None);
+
+        for (i, card) in Deck::french().iter().enumerate() {
+            assert_eq!(card.index(), i);
+            assert_eq!(FrenchCard::from_index(i), Some(*card));
+        }
+        assert_eq!(FrenchCard::from_index(FrenchCard::DECK_SIZE), None);
+        assert_eq!(Joker::from_index(Joker::Black.index()), Some(Joker::Black));
+
+        // Both jokers of a 54 cards deck have their own index.
+        for (i, card) in Deck::french_with_jokers(2).iter().enumerate() {
+            assert_eq!(card.index(), i);
+            assert_eq!(FrenchWithJoker::from_index(i), Some(*card));
+        }
+        assert_eq!(
+            FrenchWithJoker::from_index(FrenchWithJoker::DECK_SIZE),
+            None
+        );
+
+        let full = CardSet::<FrenchWithJoker>::full();
+        assert_eq!(full.len(), 54);
+        assert!(full.iter().eq(Deck::french_with_jokers(2).iter().copied()));
+        assert_eq!(CardSet::<FrenchCard>::suit_mask(Suit::Clubs).len(), 13);
+    }
+
+    #[test]
+    fn suit_masks_contain_the_cards_of_the_suit() {
+        fn check<T: Suited>() {
+            for suit in Suit::iter() {
+                let expected: CardSet<T> = CardSet::<T>::full()
+                    .iter()
+                    .filter(|c| c.suit() == suit)
+                    .collect();
+                assert_eq!(CardSet::<T>::suit_mask(suit), expected);
+            }
+        }
+
+        check::<ItalianCard>();
+        check::<FrenchCard>();
+        check::<super::SpanishCard>();
+        check::<super::GermanCard>();
+        check::<super::SwissCard>();
+        check::<crate::tressette::TressetteCard>();
+    }
+
+    #[test]
+    fn cards_sort_in_deck_order() {
+        let mut deck = Deck::french_with_jokers(1);
+        deck.shuffle_seeded(7);
+        deck.sort();
+        assert_eq!(deck[..], Deck::french_with_jokers(1)[..]);
+
+        let set: BTreeSet<ItalianCard> = Deck::italian_shuffled(3).iter().copied().collect();
+        assert!(set.iter().eq(Deck::italian().iter()));
+        assert!(Suit::Hearts < Suit::Spades);
+        assert!(ItalianRank::Ace < ItalianRank::King);
+    }
+
+    proptest! {
+        #[test]
+        fn ord_is_consistent_with_eq(a in 0..40usize, b in 0..40usize) {
+            let (a, b) = (ItalianCard::from_index(a).unwrap(), ItalianCard::from_index(b).unwrap());
+            prop_assert_eq!(a.cmp(&b) == std::cmp::Ordering::Equal, a == b);
+            prop_assert_eq!(a.cmp(&b), (a.suit(), a.rank()).cmp(&(b.suit(), b.rank())));
+        }
+
+        #[test]
+        fn set_operations_match_btree_set(a in prop::collection::vec(0..40usize, 0..40), b in prop::collection::vec(0..40usize, 0..40)) {
+            let to_cards = |bits: &[usize]| bits.iter().filter_map(|&b| ItalianCard::from_index(b)).collect::<Vec<_>>();
+            let (a, b) = (to_cards(&a), to_cards(&b));
+            let set_a: CardSet<ItalianCard> = a.iter().copied().collect();
+            let set_b: CardSet<ItalianCard> = b.iter().copied().collect();
+            let model_a: BTreeSet<usize> = a.iter().map(|c| c.index()).collect();
+            let model_b: BTreeSet<usize> = b.iter().map(|c| c.index()).collect();
+
+            let bits = |set: CardSet<ItalianCard>| set.iter().map(|c| c.index()).collect::<Vec<_>>();
+            prop_assert_eq!(set_a.len(), model_a.len());
+            prop_assert_eq!(bits(set_a | set_b), model_a.union(&model_b).copied().collect::<Vec<_>>());
+            prop_assert_eq!(bits(set_a & set_b), model_a.intersection(&model_b).copied().collect::<Vec<_>>());
+            prop_assert_eq!(bits(set_a - set_b), model_a.difference(&model_b).copied().collect::<Vec<_>>());
+            prop_assert!((set_a & set_b).is_subset(&set_a));
+
+            let mut removed = set_a;
+            for &card in &b {
+                let was_there = removed.contains(card);
+                prop_assert_eq!(removed.remove(card), was_there);
+                prop_assert!(!removed.contains(card));
+            }
+            prop_assert_eq!(removed, set_a - set_b);
+        }
+    }
+
+    #[test]
+    fn stripped_french_decks_work() {
+        assert_eq!(Deck::piquet().len(), 32);
+        assert_eq!(Deck::euchre().len(), 24);
+        assert_eq!(Deck::durak().len(), 36);
+        assert!(Deck::euchre()
+            .iter()
+            .all(|c| c.rank() == FrenchRank::Ace || c.rank() as u8 >= 9));
+
+        let doubled = DeckBuilder::new(Deck::euchre())
+            .suits(&[Suit::Hearts])
+            .copies(2)
+            .build();
+        assert_eq!(doubled.len(), 12);
+        assert_eq!(doubled[..6], doubled[6..]);
+    }
 }
@@ -0,0 +1,344 @@
+use std::{fmt::Display, str::FromStr};
+
+use strum::{EnumIter, FromRepr, IntoEnumIterator};
+
+use super::{ord_by_index, split_card, Card, Deck, ParseCardError, Ranked, Suit, Suited};
+
+/// Defines a card made of a rank and a `Suit`, together with the enum of its
+/// ranks. Ranks are listed in the order they have in a new deck, each with the
+/// number used to print it, and the letters accepted when parsing it.
+macro_rules! suited_card {
+    (
+        $(#[$card_meta:meta])*
+        $card:ident,
+        $(#[$rank_meta:meta])*
+        $rank:ident {
+            $($(#[$variant_meta:meta])* $variant:ident = $value:literal $(| $letter:literal)*,)+
+        }
+    ) => {
+        $(#[$rank_meta])*
+        #[derive(Debug, Clone, Copy, Eq, PartialEq, EnumIter, FromRepr, Hash)]
+        #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+        #[repr(u8)]
+        pub enum $rank {
+            $($(#[$variant_meta])* $variant = $value,)+
+        }
+
+        impl $rank {
+            /// Every rank, in the order they have in a new deck.
+            const ALL: &'static [$rank] = &[$($rank::$variant,)+];
+
+            /// The position of the rank inside a suit of a new deck.
+            fn position(self) -> usize {
+                // `ALL` contains every rank, so the fallback is never used.
+                Self::ALL.iter().position(|&r| r == self).unwrap_or_default()
+            }
+        }
+
+        /// Ranks are ordered as they are in a new deck.
+        impl PartialOrd for $rank {
+            fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+                Some(self.cmp(other))
+            }
+        }
+
+        impl Ord for $rank {
+            fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+                self.position().cmp(&other.position())
+            }
+        }
+
+        impl FromStr for $rank {
+            type Err = ParseCardError;
+
+            fn from_str(s: &str) -> Result<Self, Self::Err> {
+                let rank = match s.to_ascii_uppercase().as_str() {
+                    $($($letter => Some($rank::$variant),)*)+
+                    n => n.parse().ok().and_then($rank::from_repr),
+                };
+
+                rank.ok_or_else(|| ParseCardError::InvalidRank(s.to_string()))
+            }
+        }
+
+        $(#[$card_meta])*
+        #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+        pub struct $card {
+            rank: $rank,
+            suit: Suit,
+        }
+
+        impl $card {
+            /// Generates a card with the given rank and suit
+            pub fn new(rank: $rank, suit: Suit) -> Self {
+                Self { rank, suit }
+            }
+
+            /// The rank of the card.
+            pub fn rank(&self) -> $rank {
+                self.rank
+            }
+
+            /// The suit of the card.
+            pub fn suit(&self) -> Suit {
+                self.suit
+            }
+        }
+
+        impl Default for $card {
+            fn default() -> Self {
+                Self::new($rank::ALL[0], Suit::Hearts)
+            }
+        }
+
+        impl Display for $card {
+            fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                write!(f, "{}{}", self.rank as u8, self.suit)
+            }
+        }
+
+        impl FromStr for $card {
+            type Err = ParseCardError;
+
+            fn from_str(s: &str) -> Result<Self, Self::Err> {
+                let (rank, suit) = split_card(s)?;
+
+                Ok(Self::new(rank.parse()?, suit.parse()?))
+            }
+        }
+
+        impl Card for $card {
+            const DECK_SIZE: usize = 4 * $rank::ALL.len();
+
+            fn index(&self) -> usize {
+                self.suit as usize * $rank::ALL.len() + self.rank.position()
+            }
+
+            fn from_index(index: usize) -> Option<Self> {
+                let suit = Suit::from_repr(index / $rank::ALL.len())?;
+                let rank = $rank::ALL.get(index % $rank::ALL.len())?;
+                Some(Self::new(*rank, suit))
+            }
+        }
+
+        impl Suited for $card {
+            fn suit(&self) -> Suit {
+                self.suit
+            }
+        }
+
+        impl Ranked for $card {
+            type Rank = $rank;
+
+            fn rank(&self) -> $rank {
+                self.rank
+            }
+        }
+
+        ord_by_index!($card);
+
+        #[cfg(feature = "serde")]
+        super::serde_via_str!($card);
+    };
+}
+
+suited_card! {
+    /// Representation of a card that goes into a Spanish deck (baraja). The
+    /// Latin suits are mapped to `Suit` as described in its documentation:
+    /// cups are hearts, coins are diamonds, batons are clubs and swords are
+    /// spades.
+    SpanishCard,
+    /// The rank of a card in a Spanish deck. Ranks go from the ace to the 9,
+    /// then there is a jack (sota), knight (caballo) and king (rey). The 8 and
+    /// 9 are removed in the 40 cards version of the deck.
+    SpanishRank {
+        /// 1
+        Ace = 1 | "A",
+        /// 2
+        Two = 2,
+        /// 3
+        Three = 3,
+        /// 4
+        Four = 4,
+        /// 5
+        Five = 5,
+        /// 6
+        Six = 6,
+        /// 7
+        Seven = 7,
+        /// 8
+        Eight = 8,
+        /// 9
+        Nine = 9,
+        /// 10, sota
+        Jack = 10 | "J" | "S",
+        /// 11, caballo
+        Knight = 11 | "N" | "C",
+        /// 12, rey
+        King = 12 | "K" | "R",
+    }
+}
+
+suited_card! {
+    /// Representation of a card that goes into a German deck, as used in Skat.
+    /// The German suits are mapped to `Suit` as described in its
+    /// documentation: hearts are hearts, bells are diamonds, acorns are clubs
+    /// and leaves are spades.
+    GermanCard,
+    /// The rank of a card in a German deck. Ranks go from the 7 to the 10,
+    /// then there is an under knave (unter), an over knave (ober), a king and
+    /// an ace (daus).
+    GermanRank {
+        /// 7
+        Seven = 7,
+        /// 8
+        Eight = 8,
+        /// 9
+        Nine = 9,
+        /// 10
+        Ten = 10 | "T",
+        /// 11, unter
+        Unter = 11 | "U",
+        /// 12, ober
+        Ober = 12 | "O",
+        /// 13
+        King = 13 | "K",
+        /// 1, daus
+        Ace = 1 | "A" | "D",
+    }
+}
+
+suited_card! {
+    /// Representation of a card that goes into a Swiss deck, as used in Jass.
+    /// The Swiss suits are mapped to `Suit` in the same way as the German
+    /// ones: roses are hearts, bells are diamonds, acorns are clubs and
+    /// shields are spades.
+    SwissCard,
+    /// The rank of a card in a Swiss deck. Ranks go from the 6 to the 9, then
+    /// there is a banner (the 10), an under knave, an over knave, a king and
+    /// an ace.
+    SwissRank {
+        /// 6
+        Six = 6,
+        /// 7
+        Seven = 7,
+        /// 8
+        Eight = 8,
+        /// 9
+        Nine = 9,
+        /// 10, banner
+        Banner = 10 | "B" | "T",
+        /// 11, under
+        Under = 11 | "U",
+        /// 12, ober
+        Ober = 12 | "O",
+        /// 13
+        King = 13 | "K",
+        /// 1
+        Ace = 1 | "A",
+    }
+}
+
+/// Creates a deck containing every combination of the given ranks and the 4
+/// suits, in the same order used by the other decks: suit by suit.
+fn suited_deck<T: Card, R: Copy>(ranks: &[R], card: impl Fn(R, Suit) -> T) -> Deck<T> {
+    let mut cards = Vec::with_capacity(4 * ranks.len());
+    for suit in Suit::iter() {
+        for &rank in ranks {
+            cards.push(card(rank, suit));
+        }
+    }
+
+    Deck::from_vec(cards)
+}
+
+impl Deck<SpanishCard> {
+    /// Creates a new 48 cards Spanish deck.
+    pub fn spanish() -> Deck<SpanishCard> {
+        suited_deck(SpanishRank::ALL, SpanishCard::new)
+    }
+
+    /// Creates a new 40 cards Spanish deck, without the 8s and the 9s.
+    pub fn spanish_40() -> Deck<SpanishCard> {
+        let ranks = SpanishRank::iter()
+            .filter(|r| !matches!(r, SpanishRank::Eight | SpanishRank::Nine))
+            .collect::<Vec<_>>();
+        suited_deck(&ranks, SpanishCard::new)
+    }
+}
+
+impl Deck<GermanCard> {
+    /// Creates a new 32 cards German deck, as used in Skat.
+    pub fn german() -> Deck<GermanCard> {
+        suited_deck(GermanRank::ALL, GermanCard::new)
+    }
+}
+
+impl Deck<SwissCard> {
+    /// Creates a new 36 cards Swiss deck, as used in Jass.
+    pub fn swiss() -> Deck<SwissCard> {
+        suited_deck(SwissRank::ALL, SwissCard::new)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::common::cards::{Card, CardSet, Deck};
+
+    use super::{GermanCard, GermanRank, SpanishCard, SwissCard, SwissRank};
+    use crate::common::cards::Suit;
+
+    /// Checks the indices and the notation of every card of the deck.
+    fn check_deck<T: Card + std::str::FromStr<Err = crate::common::cards::ParseCardError>>(
+        deck: &Deck<T>,
+    ) {
+        for (i, card) in deck.iter().enumerate() {
+            assert_eq!(card.index(), i);
+            assert_eq!(T::from_index(i), Some(*card));
+            assert_eq!(card.to_string().parse::<T>(), Ok(*card));
+        }
+        assert_eq!(T::from_index(T::DECK_SIZE), None);
+        assert_eq!(
+            deck.iter().copied().collect::<CardSet<T>>().len(),
+            deck.len()
+        );
+    }
+
+    #[test]
+    fn regional_decks_work() {
+        assert_eq!(Deck::spanish().len(), SpanishCard::DECK_SIZE);
+        assert_eq!(Deck::spanish().len(), 48);
+        assert_eq!(Deck::spanish_40().len(), 40);
+        assert_eq!(Deck::german().len(), 32);
+        assert_eq!(Deck::swiss().len(), 36);
+
+        check_deck(&Deck::spanish());
+        check_deck(&Deck::german());
+        check_deck(&Deck::swiss());
+    }
+
+    #[test]
+    fn regional_ranks_accept_letters() {
+        assert_eq!(
+            "UD".parse::<GermanCard>(),
+            Ok(GermanCard::new(GermanRank::Unter, Suit::Diamonds))
+        );
+        assert_eq!(
+            "BS".parse::<SwissCard>(),
+            Ok(SwissCard::new(SwissRank::Banner, Suit::Spades))
+        );
+        assert!("8C".parse::<SpanishCard>().is_ok());
+        assert!("8C".parse::<SwissCard>().is_ok());
+        assert!("6C".parse::<GermanCard>().is_err());
+    }
+
+    #[test]
+    fn regional_ranks_follow_deck_order() {
+        assert!(GermanRank::King < GermanRank::Ace);
+        assert!(SwissRank::Six < SwissRank::Banner);
+        let mut deck = Deck::german();
+        deck.reverse();
+        deck.sort();
+        assert_eq!(deck[..], Deck::german()[..]);
+    }
+}
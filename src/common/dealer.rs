@@ -0,0 +1,430 @@
+use super::{
+    cards::{Card, CardSet, Deck},
+    hands::{Player, PlayerId, TrickTakingGame},
+};
+use crate::error::ShuftError;
+
+/// A single step of a `DealPattern`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DealStep {
+    /// Every player receives a packet with the given number of cards, one
+    /// player after the other.
+    Packet(usize),
+    /// The given number of cards is put in the talon (also called kitty or
+    /// widow).
+    Talon(usize),
+}
+
+/// Where the cards left in the deck after the last step of a `DealPattern`
+/// end up.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Leftover {
+    /// The cards stay in the deck, which is returned as the stock.
+    #[default]
+    Stock,
+    /// The cards are added to the talon.
+    Talon,
+}
+
+/// The order in which players receive their cards.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Direction {
+    /// From each player to the one with the next `PlayerId`.
+    #[default]
+    Clockwise,
+    /// From each player to the one with the previous `PlayerId`.
+    CounterClockwise,
+}
+
+/// A declarative description of how the cards are dealt: a sequence of steps
+/// that give packets of cards to every player or put some cards in the talon.
+///
+/// # Examples
+///
+/// ```
+/// use shuftlib::common::dealer::{DealPattern, DealStep};
+///
+/// // Skat: 3 cards each, 2 in the skat, then 4 and 3 cards each.
+/// let skat = DealPattern::new()
+///     .packet(3)
+///     .talon(2)
+///     .packet(4)
+///     .packet(3);
+/// assert_eq!(skat.cards_needed(3), 32);
+///
+/// // Tressette: two rounds of 5 cards each.
+/// assert_eq!(DealPattern::packets(5, 2).steps(), [DealStep::Packet(5); 2]);
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct DealPattern {
+    steps: Vec<DealStep>,
+    leftover: Leftover,
+}
+
+impl DealPattern {
+    /// Creates an empty pattern, to be filled with `packet` and `talon`.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// A pattern made of `rounds` packets of `size` cards for each player.
+    pub fn packets(size: usize, rounds: usize) -> Self {
+        Self {
+            steps: vec![DealStep::Packet(size); rounds],
+            leftover: Leftover::Stock,
+        }
+    }
+
+    /// A pattern where cards are dealt one at a time, until every player has
+    /// `cards` cards.
+    pub fn round_robin(cards: usize) -> Self {
+        Self::packets(1, cards)
+    }
+
+    /// A pattern made of one round for each of the given packet sizes, for
+    /// example 3-2-3.
+    pub fn rounds(sizes: &[usize]) -> Self {
+        sizes.iter().fold(Self::new(), |p, &size| p.packet(size))
+    }
+
+    /// Adds a round where every player receives `size` cards.
+    pub fn packet(mut self, size: usize) -> Self {
+        self.steps.push(DealStep::Packet(size));
+        self
+    }
+
+    /// Adds a step where `size` cards are put in the talon.
+    pub fn talon(mut self, size: usize) -> Self {
+        self.steps.push(DealStep::Talon(size));
+        self
+    }
+
+    /// Sets where the cards left after the last step end up.
+    pub fn leftover(mut self, leftover: Leftover) -> Self {
+        self.leftover = leftover;
+        self
+    }
+
+    /// The steps of this pattern.
+    pub fn steps(&self) -> &[DealStep] {
+        &self.steps
+    }
+
+    /// The number of cards each player receives by following this pattern.
+    pub fn cards_per_player(&self) -> usize {
+        self.steps
+            .iter()
+            .map(|step| match step {
+                DealStep::Packet(size) => *size,
+                DealStep::Talon(_) => 0,
+            })
+            .sum()
+    }
+
+    /// The number of cards needed to follow this pattern with the given
+    /// number of players.
+    pub fn cards_needed(&self, players: usize) -> usize {
+        self.steps
+            .iter()
+            .map(|step| match step {
+                DealStep::Packet(size) => size * players,
+                DealStep::Talon(size) => *size,
+            })
+            .sum()
+    }
+}
+
+/// The cards that haven't been given to any player after a deal.
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Deal<T: Card> {
+    stock: Deck<T>,
+    talon: Deck<T>,
+}
+
+impl<T: Card> Deal<T> {
+    /// The cards left in the deck, with the top of the deck unchanged.
+    pub fn stock(&self) -> &Deck<T> {
+        &self.stock
+    }
+
+    /// The cards put in the talon, in the order they have been dealt.
+    pub fn talon(&self) -> &Deck<T> {
+        &self.talon
+    }
+
+    /// Splits the deal into the stock and the talon.
+    pub fn into_parts(self) -> (Deck<T>, Deck<T>) {
+        (self.stock, self.talon)
+    }
+}
+
+/// Deals a deck to the players of a game, following a `DealPattern`.
+///
+/// # Examples
+///
+/// ```
+/// use shuftlib::common::{cards::Deck, dealer::{DealPattern, Dealer}, hands::{Player, PlayerId}};
+/// use shuftlib::tressette::{TressetteCard, TressetteRules};
+///
+/// let mut players: [Player<TressetteRules>; 4] =
+///     std::array::from_fn(|i| Player::new(PlayerId::new(i).unwrap()));
+/// let deck = Deck::from_vec(Deck::italian().iter().map(|&c| TressetteCard::from(c)).collect());
+///
+/// let deal = Dealer::new(DealPattern::packets(5, 2))
+///     .deal(deck, &mut players, PlayerId::new(1).unwrap())
+///     .unwrap();
+///
+/// assert!(deal.stock().is_empty());
+/// assert!(players.iter().all(|p| p.hand().len() == 10));
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct Dealer {
+    pattern: DealPattern,
+    direction: Direction,
+}
+
+impl Dealer {
+    /// Creates a dealer that follows the given pattern, clockwise.
+    pub fn new(pattern: DealPattern) -> Self {
+        Self {
+            pattern,
+            direction: Direction::Clockwise,
+        }
+    }
+
+    /// Sets the direction in which the cards are dealt.
+    pub fn direction(mut self, direction: Direction) -> Self {
+        self.direction = direction;
+        self
+    }
+
+    /// The pattern followed by this dealer.
+    pub fn pattern(&self) -> &DealPattern {
+        &self.pattern
+    }
+
+    /// Deals the cards from the top of `deck` to `players`, following the
+    /// pattern of this dealer. In every round, `first` is the first player to
+    /// receive a packet. `players` must be sorted by `PlayerId`, with one
+    /// player for each id of the game `first` belongs to.
+    ///
+    /// # Errors
+    ///
+    /// Fails if `players` doesn't contain exactly one player for each id, if
+    /// the deck doesn't contain enough cards to follow the pattern, or if a
+    /// player would receive a card they already hold. In all these cases no
+    /// card is dealt.
+    pub fn deal<G>(
+        &self,
+        mut deck: Deck<G::CardType>,
+        players: &mut [Player<G>],
+        first: PlayerId<G>,
+    ) -> Result<Deal<G::CardType>, ShuftError>
+    where
+        G: TrickTakingGame,
+    {
+        let seats = first.players();
+        if players.len() != seats {
+            return Err(ShuftError::InvalidPlayers {
+                found: players.len(),
+                expected: seats,
+            });
+        }
+        if let Some((i, _)) = players.iter().enumerate().find(|(i, p)| *p.id() != *i) {
+            return Err(ShuftError::InvalidPlayerId {
+                id: i,
+                players: seats,
+            });
+        }
+
+        let needed = self.pattern.cards_needed(seats);
+        if deck.len() < needed {
+            return Err(ShuftError::NotEnoughCards {
+                needed,
+                available: deck.len(),
+            });
+        }
+
+        // The hands are computed before giving any card, so that nothing is
+        // dealt if a player would receive a duplicate.
+        let mut hands: Vec<CardSet<G::CardType>> = players.iter().map(|p| *p.hand()).collect();
+        let mut talon = Deck::new();
+        for &step in self.pattern.steps() {
+            match step {
+                DealStep::Packet(size) => {
+                    let mut seat = *first;
+                    for _ in 0..seats {
+                        let top = deck.len() - size;
+                        for card in deck.drain(top..).rev() {
+                            if !hands[seat].insert(card) {
+                                return Err(ShuftError::DuplicateCard(card.to_string()));
+                            }
+                        }
+                        seat = match self.direction {
+                            Direction::Clockwise => (seat + 1) % seats,
+                            Direction::CounterClockwise => (seat + seats - 1) % seats,
+                        };
+                    }
+                }
+                DealStep::Talon(size) => {
+                    let top = deck.len() - size;
+                    talon.extend(deck.drain(top..).rev());
+                }
+            }
+        }
+
+        if self.pattern.leftover == Leftover::Talon {
+            talon.extend(deck.drain(..).rev());
+        }
+
+        for (player, hand) in players.iter_mut().zip(hands) {
+            for card in hand.difference(player.hand()) {
+                player.give(card);
+            }
+        }
+
+        Ok(Deal { stock: deck, talon })
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use crate::{
+        common::{
+            cards::{Card, CardSet, Deck, GermanCard},
+            hands::{HandContext, Player, PlayerId, TrickTakingGame},
+        },
+        error::ShuftError,
+    };
+
+    use super::{DealPattern, Dealer, Direction, Leftover};
+
+    #[derive(Debug, Clone, Copy)]
+    struct Skat;
+
+    impl TrickTakingGame for Skat {
+        type CardType = GermanCard;
+        type Contract = ();
+        type Bid = ();
+
+        const PLAYERS: usize = 3;
+
+        const TRICKS: usize = 10;
+
+        fn determine_taker(
+            _cards: &[Self::CardType],
+            first_to_play: PlayerId<Self>,
+            _context: &HandContext<Self>,
+        ) -> PlayerId<Self> {
+            first_to_play
+        }
+    }
+
+    fn players() -> [Player<Skat>; Skat::PLAYERS] {
+        std::array::from_fn(|i| Player::new(PlayerId::new(i).unwrap()))
+    }
+
+    #[test]
+    fn deals_following_the_pattern() {
+        let pattern = DealPattern::new().packet(3).talon(2).packet(4).packet(3);
+        let mut players = players();
+        let deal = Dealer::new(pattern)
+            .deal(Deck::german(), &mut players, PlayerId::new(0).unwrap())
+            .unwrap();
+
+        assert!(deal.stock().is_empty());
+        assert_eq!(deal.talon().len(), 2);
+        assert!(players.iter().all(|p| p.hand().len() == 10));
+
+        // The first cards on top of the deck go to the first player, then
+        // the talon is dealt after the first round.
+        let deck = Deck::german();
+        let mut top = deck.iter().rev().copied();
+        let first_packet: CardSet<GermanCard> = top.by_ref().take(3).collect();
+        assert!(first_packet.is_subset(players[0].hand()));
+        let second_packet: CardSet<GermanCard> = top.by_ref().take(3).collect();
+        assert!(second_packet.is_subset(players[1].hand()));
+        top.nth(2);
+        assert_eq!(deal.talon().to_vec(), top.take(2).collect::<Vec<_>>());
+
+        let all = players
+            .iter()
+            .fold(CardSet::new(), |set, p| set | *p.hand())
+            | deal.talon().iter().copied().collect();
+        assert_eq!(all.len(), GermanCard::DECK_SIZE);
+    }
+
+    #[test]
+    fn deals_counter_clockwise_and_keeps_the_stock() {
+        let mut players = players();
+        let deal = Dealer::new(DealPattern::round_robin(5))
+            .direction(Direction::CounterClockwise)
+            .deal(Deck::german(), &mut players, PlayerId::new(0).unwrap())
+            .unwrap();
+
+        assert_eq!(deal.stock().len(), 17);
+        assert_eq!(deal.stock()[..], Deck::german()[..17]);
+        let deck = Deck::german();
+        assert!(players[2].hand().contains(deck[deck.len() - 2]));
+        assert!(players[1].hand().contains(deck[deck.len() - 3]));
+    }
+
+    #[test]
+    fn leftover_can_go_to_the_talon() {
+        let mut players = players();
+        let deal = Dealer::new(DealPattern::packets(5, 2).leftover(Leftover::Talon))
+            .deal(Deck::german(), &mut players, PlayerId::new(2).unwrap())
+            .unwrap();
+
+        assert!(deal.stock().is_empty());
+        assert_eq!(deal.talon().len(), 2);
+    }
+
+    #[test]
+    fn fails_without_enough_cards() {
+        let mut players = players();
+        let result = Dealer::new(DealPattern::packets(11, 1)).deal(
+            Deck::german(),
+            &mut players,
+            PlayerId::new(0).unwrap(),
+        );
+
+        assert_eq!(
+            result.unwrap_err(),
+            ShuftError::NotEnoughCards {
+                needed: 33,
+                available: 32
+            }
+        );
+        assert!(players.iter().all(|p| p.hand().is_empty()));
+
+        let result = Dealer::new(DealPattern::packets(1, 1)).deal(
+            Deck::german(),
+            &mut players[..2],
+            PlayerId::new(0).unwrap(),
+        );
+        assert!(matches!(result, Err(ShuftError::InvalidPlayers { .. })));
+    }
+
+    #[test]
+    fn fails_on_duplicates() {
+        let mut players = players();
+        let deck = Deck::german();
+        let top = deck[deck.len() - 1];
+        players[0].give(top);
+        let result = Dealer::new(DealPattern::round_robin(3)).deal(
+            deck,
+            &mut players,
+            PlayerId::new(0).unwrap(),
+        );
+
+        assert_eq!(
+            result.unwrap_err(),
+            ShuftError::DuplicateCard(top.to_string())
+        );
+        assert_eq!(players[0].hand().len(), 1);
+        assert!(players[1..].iter().all(|p| p.hand().is_empty()));
+    }
+}
@@ -0,0 +1,481 @@
+use std::{
+    error::Error,
+    fmt::{Debug, Display},
+};
+
+use super::{
+    cards::CardSet,
+    hands::{Player, PlayerId, TrickTakingGame, MAX_PLAYERS},
+};
+use crate::error::ShuftError;
+
+/// How cards are exchanged before the first trick of a hand.
+///
+/// # Examples
+///
+/// ```
+/// use shuftlib::common::exchange::ExchangePattern;
+///
+/// // Hearts: three cards are passed to the left, to the right, across and
+/// // then kept, one hand after the other.
+/// let patterns: Vec<_> = (0..5).map(|hand| ExchangePattern::rotating(hand, 3, 4)).collect();
+/// assert_eq!(patterns[0], ExchangePattern::Pass { cards: 3, offset: 1 });
+/// assert_eq!(patterns[1], ExchangePattern::Pass { cards: 3, offset: 3 });
+/// assert_eq!(patterns[2], ExchangePattern::Pass { cards: 3, offset: 2 });
+/// assert_eq!(patterns[3], ExchangePattern::Hold);
+/// assert_eq!(patterns[4], patterns[0]);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum ExchangePattern {
+    /// No cards are exchanged.
+    #[default]
+    Hold,
+    /// Every player passes `cards` cards to the player `offset` seats after
+    /// them: 1 is the player on the left, and with 4 players 2 is the
+    /// partner and 3 the player on the right.
+    Pass {
+        /// The number of cards passed by each player.
+        cards: usize,
+        /// The distance between a player and the one receiving their cards.
+        offset: usize,
+    },
+    /// The player at seat `player` takes the talon and then discards `cards`
+    /// cards, like the declarer in skat or tarot.
+    Talon {
+        /// The seat of the player taking the talon.
+        player: usize,
+        /// The number of cards discarded.
+        cards: usize,
+    },
+}
+
+impl ExchangePattern {
+    /// The pattern of hearts for the hand with index `hand`, where `cards`
+    /// cards are passed to the left, to the right, across (only with an even
+    /// number of players) and then kept, cycling from hand to hand. Passing no
+    /// cards is the same as keeping them.
+    pub fn rotating(hand: usize, cards: usize, players: usize) -> Self {
+        if cards == 0 {
+            return ExchangePattern::Hold;
+        }
+
+        let mut offsets = vec![1, players.saturating_sub(1)];
+        if players.is_multiple_of(2) && players > 2 {
+            offsets.push(players / 2);
+        }
+
+        match offsets.get(hand % (offsets.len() + 1)) {
+            Some(&offset) => ExchangePattern::Pass { cards, offset },
+            None => ExchangePattern::Hold,
+        }
+    }
+
+    /// The number of cards the player at `seat` has to select.
+    pub fn cards_for(&self, seat: usize) -> usize {
+        match *self {
+            ExchangePattern::Hold => 0,
+            ExchangePattern::Pass { cards, .. } => cards,
+            ExchangePattern::Talon { player, cards } if player == seat => cards,
+            ExchangePattern::Talon { .. } => 0,
+        }
+    }
+}
+
+/// The reasons why the cards selected by a player can't be exchanged.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ExchangeError {
+    /// The player selected the wrong number of cards.
+    WrongCount {
+        /// The number of cards the player has to select.
+        expected: usize,
+        /// The number of cards selected.
+        found: usize,
+    },
+    /// The player doesn't hold some of the selected cards.
+    NotInHand,
+    /// The player already selected their cards.
+    AlreadySelected,
+    /// No exchange is in progress.
+    NoExchange,
+}
+
+impl Display for ExchangeError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ExchangeError::WrongCount { expected, found } => write!(
+                f,
+                "{} cards have to be selected, but {} were selected",
+                expected, found
+            ),
+            ExchangeError::NotInHand => write!(f, "the player doesn't hold the selected cards"),
+            ExchangeError::AlreadySelected => write!(f, "the player already selected their cards"),
+            ExchangeError::NoExchange => write!(f, "no exchange is in progress"),
+        }
+    }
+}
+
+impl Error for ExchangeError {}
+
+/// The exchange of cards before the first trick of a hand. Every player
+/// selects the cards they give away, in any order, without seeing the ones
+/// selected by the others; once every selection has been made, the cards are
+/// transferred all at once, following the `ExchangePattern`.
+///
+/// # Examples
+///
+/// ```
+/// use shuftlib::common::{cards::{CardSet, ItalianRank, Suit}, exchange::{Exchange, ExchangePattern}, hands::{Player, PlayerId}};
+/// use shuftlib::tressette::{TressetteCard, TressetteRules};
+///
+/// let mut players: Vec<Player<TressetteRules>> =
+///     (0..4).map(|i| Player::new(PlayerId::new(i).unwrap())).collect();
+/// for (player, suit) in players.iter_mut().zip([Suit::Hearts, Suit::Clubs, Suit::Spades, Suit::Diamonds]) {
+///     player.give(TressetteCard::new(ItalianRank::Ace, suit));
+///     player.give(TressetteCard::new(ItalianRank::Two, suit));
+/// }
+///
+/// // Partners swap their aces.
+/// let mut exchange = Exchange::new(ExchangePattern::Pass { cards: 1, offset: 2 });
+/// for player in &players {
+///     let ace = player.hand().iter().next().unwrap();
+///     exchange.select(player, [ace].into_iter().collect()).unwrap();
+/// }
+/// exchange.apply(&mut players).unwrap();
+///
+/// let ace_of_spades = TressetteCard::new(ItalianRank::Ace, Suit::Spades);
+/// assert!(players[0].hand().contains(ace_of_spades));
+/// ```
+pub struct Exchange<G: TrickTakingGame> {
+    pattern: ExchangePattern,
+    talon: CardSet<G::CardType>,
+    selections: [Option<CardSet<G::CardType>>; MAX_PLAYERS],
+}
+
+impl<G: TrickTakingGame> Exchange<G> {
+    /// Creates an exchange following `pattern`, where nobody selected their
+    /// cards yet.
+    pub fn new(pattern: ExchangePattern) -> Self {
+        Self {
+            pattern,
+            talon: CardSet::new(),
+            selections: [None; MAX_PLAYERS],
+        }
+    }
+
+    /// Sets the talon taken by the player of `ExchangePattern::Talon`, who
+    /// can discard its cards too.
+    pub fn with_talon(mut self, talon: CardSet<G::CardType>) -> Self {
+        self.talon = talon;
+        self
+    }
+
+    /// The pattern of this exchange.
+    pub fn pattern(&self) -> ExchangePattern {
+        self.pattern
+    }
+
+    /// The talon of this exchange.
+    pub fn talon(&self) -> CardSet<G::CardType> {
+        self.talon
+    }
+
+    /// The cards selected by the player at `seat`, if they already did.
+    pub fn selection(&self, seat: PlayerId<G>) -> Option<CardSet<G::CardType>> {
+        self.selections[*seat]
+    }
+
+    /// Whether the player at `seat` still has to select their cards.
+    pub fn is_waiting_for(&self, seat: PlayerId<G>) -> bool {
+        self.pattern.cards_for(*seat) > 0 && self.selections[*seat].is_none()
+    }
+
+    /// Whether every player who has to select cards did it, for a game with
+    /// the given number of players.
+    pub fn is_complete(&self, players: usize) -> bool {
+        (0..players)
+            .all(|seat| self.pattern.cards_for(seat) == 0 || self.selections[seat].is_some())
+    }
+
+    /// The cards `player` can select from: their hand, plus the talon if
+    /// they're taking it.
+    fn available(&self, player: &Player<G>) -> CardSet<G::CardType> {
+        match self.pattern {
+            ExchangePattern::Talon { player: seat, .. } if seat == *player.id() => {
+                player.hand().union(&self.talon)
+            }
+            _ => *player.hand(),
+        }
+    }
+
+    /// Selects the cards `player` gives away. Nothing is transferred until
+    /// `apply` is called.
+    ///
+    /// # Errors
+    ///
+    /// Fails with `ShuftError::IllegalExchange` if the player already
+    /// selected their cards, selected the wrong number of cards, or doesn't
+    /// hold some of them.
+    pub fn select(
+        &mut self,
+        player: &Player<G>,
+        cards: CardSet<G::CardType>,
+    ) -> Result<(), ShuftError> {
+        let seat = *player.id();
+        if self.selections[seat].is_some() {
+            return Err(ExchangeError::AlreadySelected.into());
+        }
+        let expected = self.pattern.cards_for(seat);
+        if cards.len() != expected {
+            return Err(ExchangeError::WrongCount {
+                expected,
+                found: cards.len(),
+            }
+            .into());
+        }
+        if !cards.is_subset(&self.available(player)) {
+            return Err(ExchangeError::NotInHand.into());
+        }
+
+        self.selections[seat] = Some(cards);
+        Ok(())
+    }
+
+    /// Transfers the selected cards, returning the ones discarded with
+    /// `ExchangePattern::Talon`, which is empty for the other patterns. Either
+    /// every card is transferred or none is.
+    ///
+    /// # Errors
+    ///
+    /// Fails with `ShuftError::IncompleteExchange` if some players didn't
+    /// select their cards yet, with `ShuftError::IllegalExchange` if a player
+    /// doesn't hold the cards they selected anymore, and with
+    /// `ShuftError::DuplicateCard` if a player would receive a card they
+    /// already hold.
+    pub fn apply(self, players: &mut [Player<G>]) -> Result<CardSet<G::CardType>, ShuftError> {
+        if !self.is_complete(players.len()) {
+            return Err(ShuftError::IncompleteExchange);
+        }
+        let selected = |seat: usize| self.selections[seat].unwrap_or_default();
+
+        let mut hands = Vec::with_capacity(players.len());
+        for (seat, player) in players.iter().enumerate() {
+            if !selected(seat).is_subset(&self.available(player)) {
+                return Err(ExchangeError::NotInHand.into());
+            }
+            let kept = self.available(player).difference(&selected(seat));
+            let received = match self.pattern {
+                ExchangePattern::Pass { offset, .. } => {
+                    selected((seat + players.len() - offset % players.len()) % players.len())
+                }
+                ExchangePattern::Hold | ExchangePattern::Talon { .. } => CardSet::new(),
+            };
+            if let Some(card) = kept.intersection(&received).iter().next() {
+                return Err(ShuftError::DuplicateCard(card.to_string()));
+            }
+            hands.push(kept.union(&received));
+        }
+
+        for (player, hand) in players.iter_mut().zip(hands) {
+            for card in player.hand().difference(&hand) {
+                player.remove(card);
+            }
+            for card in hand.difference(player.hand()) {
+                player.give(card);
+            }
+        }
+
+        Ok(match self.pattern {
+            ExchangePattern::Talon { player, .. } => selected(player),
+            _ => CardSet::new(),
+        })
+    }
+}
+
+impl<G: TrickTakingGame> Clone for Exchange<G> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<G: TrickTakingGame> Copy for Exchange<G> {}
+
+impl<G: TrickTakingGame> Debug for Exchange<G> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Exchange")
+            .field("pattern", &self.pattern)
+            .field("talon", &self.talon)
+            .field("selections", &self.selections)
+            .finish()
+    }
+}
+
+#[cfg(feature = "serde")]
+mod serde_impls {
+    use serde::{ser::SerializeStruct, Deserialize, Deserializer, Serialize, Serializer};
+
+    use super::{Exchange, ExchangePattern};
+    use crate::common::{
+        cards::CardSet,
+        hands::{TrickTakingGame, MAX_PLAYERS},
+    };
+
+    impl<G> Serialize for Exchange<G>
+    where
+        G: TrickTakingGame,
+        G::CardType: Serialize,
+    {
+        fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+            let mut state = serializer.serialize_struct("Exchange", 3)?;
+            state.serialize_field("pattern", &self.pattern)?;
+            state.serialize_field("talon", &self.talon)?;
+            state.serialize_field("selections", &self.selections)?;
+            state.end()
+        }
+    }
+
+    #[derive(Deserialize)]
+    #[serde(rename = "Exchange")]
+    struct ExchangeRepr<S> {
+        pattern: ExchangePattern,
+        talon: S,
+        selections: [Option<S>; MAX_PLAYERS],
+    }
+
+    impl<'de, G> Deserialize<'de> for Exchange<G>
+    where
+        G: TrickTakingGame,
+        G::CardType: Deserialize<'de>,
+    {
+        fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+            let ExchangeRepr {
+                pattern,
+                talon,
+                selections,
+            } = ExchangeRepr::<CardSet<G::CardType>>::deserialize(deserializer)?;
+            Ok(Exchange {
+                pattern,
+                talon,
+                selections,
+            })
+        }
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use proptest::prelude::*;
+
+    use crate::{
+        common::{
+            cards::{CardSet, Deck},
+            hands::{Player, PlayerId},
+        },
+        error::ShuftError,
+        tressette::{TressetteCard, TressetteRules},
+    };
+
+    use super::{Exchange, ExchangeError, ExchangePattern};
+
+    fn players(seed: u64) -> Vec<Player<TressetteRules>> {
+        let mut deck = Deck::tressette();
+        deck.shuffle_seeded(seed);
+        (0..4)
+            .map(|i| {
+                let mut player = Player::new(PlayerId::new(i).unwrap());
+                for card in &deck[i * 10..i * 10 + 10] {
+                    player.give(*card);
+                }
+                player
+            })
+            .collect()
+    }
+
+    fn first(hand: &CardSet<TressetteCard>, cards: usize) -> CardSet<TressetteCard> {
+        hand.iter().take(cards).collect()
+    }
+
+    #[test]
+    fn selections_are_validated() {
+        let players = players(0);
+        let mut exchange = Exchange::new(ExchangePattern::Pass {
+            cards: 3,
+            offset: 1,
+        });
+        assert_eq!(
+            exchange.select(&players[0], first(players[0].hand(), 2)),
+            Err(ShuftError::IllegalExchange(ExchangeError::WrongCount {
+                expected: 3,
+                found: 2
+            }))
+        );
+        assert_eq!(
+            exchange.select(&players[0], first(players[1].hand(), 3)),
+            Err(ExchangeError::NotInHand.into())
+        );
+        exchange
+            .select(&players[0], first(players[0].hand(), 3))
+            .unwrap();
+        assert_eq!(
+            exchange.select(&players[0], first(players[0].hand(), 3)),
+            Err(ExchangeError::AlreadySelected.into())
+        );
+        assert!(!exchange.is_complete(4));
+        assert!(exchange.is_waiting_for(PlayerId::new(1).unwrap()));
+        assert_eq!(
+            exchange.apply(&mut players.clone()),
+            Err(ShuftError::IncompleteExchange)
+        );
+    }
+
+    #[test]
+    fn talon_is_taken_and_discarded() {
+        let mut players = players(1);
+        let talon: CardSet<TressetteCard> = players[3].hand().iter().take(2).collect();
+        for card in talon {
+            players[3].remove(card);
+        }
+
+        let mut exchange = Exchange::new(ExchangePattern::Talon {
+            player: 1,
+            cards: 2,
+        })
+        .with_talon(talon);
+        assert!(exchange.is_complete(1));
+        assert!(!exchange.is_complete(4));
+        // The player taking the talon can discard its cards.
+        exchange.select(&players[1], talon).unwrap();
+        let discarded = exchange.apply(&mut players).unwrap();
+        assert_eq!(discarded, talon);
+        assert_eq!(players[1].hand().len(), 10);
+    }
+
+    proptest! {
+        #[test]
+        fn passing_keeps_every_card(seed in any::<u64>(), hand in 0usize..8) {
+            let mut players = players(seed);
+            let before: Vec<_> = players.iter().map(|p| *p.hand()).collect();
+            let pattern = ExchangePattern::rotating(hand, 3, 4);
+
+            let mut exchange = Exchange::new(pattern);
+            for player in &players {
+                let cards = pattern.cards_for(*player.id());
+                exchange.select(player, first(player.hand(), cards)).unwrap();
+            }
+            exchange.apply(&mut players).unwrap();
+
+            let all = before.iter().fold(CardSet::new(), |all, hand| all.union(hand));
+            prop_assert_eq!(players.iter().fold(CardSet::new(), |all, p| all.union(p.hand())), all);
+            for (seat, player) in players.iter().enumerate() {
+                prop_assert_eq!(player.hand().len(), 10);
+                if let ExchangePattern::Pass { offset, .. } = pattern {
+                    let from = (seat + 4 - offset) % 4;
+                    prop_assert!(first(&before[from], 3).is_subset(player.hand()));
+                } else {
+                    prop_assert_eq!(*player.hand(), before[seat]);
+                }
+            }
+        }
+    }
+}
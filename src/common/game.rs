@@ -0,0 +1,1179 @@
+use std::{fmt::Debug, mem};
+
+use rand::SeedableRng;
+use rand_chacha::ChaCha8Rng;
+
+use super::{
+    auction::{Auction, AuctionRules, Call, CallError},
+    cards::{CardSet, Deck},
+    dealer::{Deal, Dealer},
+    exchange::{Exchange, ExchangeError, ExchangePattern},
+    hands::{
+        Declarations, Hand, HandContext, OngoingHand, OngoingTrick, Player, PlayerId,
+        TrickTakingGame,
+    },
+    stock::Stock,
+};
+use crate::error::ShuftError;
+
+/// The rules needed to play a complete game with `Game`, on top of the ones
+/// needed to play a single trick: which cards are used, how they are dealt,
+/// how hands are scored and when the game is over.
+pub trait GameRules: TrickTakingGame {
+    /// The score accumulated during the game, for example the points of each
+    /// team. A new game starts from the default value.
+    type Score: Clone + Debug + Default;
+
+    /// A new deck containing every card used in a hand. `Game` shuffles it
+    /// before dealing.
+    fn deck(&self) -> Deck<Self::CardType>;
+
+    /// The dealer used at the beginning of every hand, for the given number
+    /// of players. Every player plays one trick for each card they receive,
+    /// including the ones drawn from the stock.
+    fn dealer(&self, players: usize) -> Dealer;
+
+    /// The auction played after dealing the hand with index `hand`, before
+    /// exchanging cards, usually `Auction::new(dealer)` for rules that
+    /// implement `AuctionRules`. The calls are made with `Game::call`. By
+    /// default, there's no auction.
+    fn auction(&self, _hand: usize, _dealer: PlayerId<Self>) -> Option<Auction<Self>> {
+        None
+    }
+
+    /// The trumps of a hand, decided once the cards have been dealt and the
+    /// auction, if any, has been won with `contract`, for example by turning
+    /// the top card of the stock or from the strain of the contract. `Game`
+    /// adds the contract to the returned context, so that it's available
+    /// while playing and in `score_hand`. By default there are no trumps.
+    fn hand_context(
+        &self,
+        _deal: &Deal<Self::CardType>,
+        _contract: Option<Self::Contract>,
+    ) -> HandContext<Self> {
+        HandContext::new()
+    }
+
+    /// The stock the players draw from after every trick, made of cards left
+    /// after dealing to the given number of players. Its cards must be
+    /// enough to give the same number of them to every player. By default,
+    /// there's no stock and the undealt cards aren't used.
+    fn stock(
+        &self,
+        _deal: &Deal<Self::CardType>,
+        _players: usize,
+    ) -> Option<Stock<Self::CardType>> {
+        None
+    }
+
+    /// How cards are exchanged after dealing the hand with index `hand`, for
+    /// the given number of players. The pattern can change from hand to
+    /// hand, like in hearts. By default, no cards are exchanged.
+    fn exchange_pattern(&self, _hand: usize, _players: usize) -> ExchangePattern {
+        ExchangePattern::Hold
+    }
+
+    /// Adds the points scored in `hand` to `score`. The contract won in the
+    /// auction, if any, is in the context of the hand.
+    fn score_hand(&self, hand: &Hand<Self>, score: &mut Self::Score);
+
+    /// Whether the game is over with the given score.
+    fn is_completed(&self, score: &Self::Score) -> bool;
+}
+
+/// The phase of a `Game`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Phase {
+    /// The players are making their calls in the auction of the hand, see
+    /// `Game::call`.
+    Bidding,
+    /// The players are selecting the cards to exchange before the first
+    /// trick, see `Game::exchange`.
+    Exchanging,
+    /// The players are playing the tricks of a hand.
+    Playing,
+    /// The game is over: no more moves can be made.
+    Completed,
+}
+
+/// A complete game, played one move at a time. It owns the players and deals
+/// a new hand whenever the previous one is over, rotating the dealer. If the
+/// rules have an auction, it's played before the hand, and when every player
+/// passes the next dealer deals again. The player after the dealer leads the first trick of a hand, then every trick
+/// is led by the taker of the previous one, and if the hand has a stock every
+/// player draws a card from it, starting from the taker. The score is updated
+/// at the end of every hand, until the rules declare the game completed.
+///
+/// # Examples
+///
+/// ```
+/// use shuftlib::common::game::{Game, Phase};
+/// use shuftlib::tressette::TressetteRules;
+///
+/// let mut game = Game::new(TressetteRules::default(), 42).unwrap();
+/// while game.phase() != Phase::Completed {
+///     let card = game.legal_moves().iter().next().unwrap();
+///     game.apply(card).unwrap();
+/// }
+///
+/// assert!(game.rules().is_completed(game.score()));
+/// ```
+#[derive(Debug, Clone)]
+pub struct Game<G: GameRules> {
+    rules: G,
+    rng: ChaCha8Rng,
+    players: Vec<Player<G>>,
+    dealer: PlayerId<G>,
+    hand: OngoingHand<G>,
+    deal: Option<Deal<G::CardType>>,
+    auction: Option<Auction<G>>,
+    exchange: Option<Exchange<G>>,
+    stock: Option<Stock<G::CardType>>,
+    hands: Vec<Hand<G>>,
+    score: G::Score,
+    phase: Phase,
+}
+
+impl<G: GameRules> Game<G> {
+    /// Creates a game with the default number of players and deals the first
+    /// hand. The decks are shuffled with a random number generator seeded
+    /// with `seed`, so that the same seed and moves always produce the same
+    /// game.
+    ///
+    /// # Errors
+    ///
+    /// Fails if the first hand can't be dealt, see `Dealer::deal`.
+    pub fn new(rules: G, seed: u64) -> Result<Self, ShuftError> {
+        Self::with_players(rules, G::PLAYERS, seed)
+    }
+
+    /// Same as `new`, but for the given number of players.
+    ///
+    /// # Errors
+    ///
+    /// Fails if the game can't be played by that many players, or if the
+    /// first hand can't be dealt.
+    pub fn with_players(rules: G, players: usize, seed: u64) -> Result<Self, ShuftError> {
+        let invalid = ShuftError::InvalidPlayers {
+            found: players,
+            expected: G::PLAYERS,
+        };
+        let dealer = PlayerId::with_players(0, players).ok_or(invalid)?;
+        let players = (0..players)
+            .filter_map(|i| dealer.other(i))
+            .map(Player::new)
+            .collect();
+
+        let mut game = Self {
+            rules,
+            rng: ChaCha8Rng::seed_from_u64(seed),
+            players,
+            dealer,
+            hand: OngoingHand::with_tricks(dealer, 0),
+            deal: None,
+            auction: None,
+            exchange: None,
+            stock: None,
+            hands: Vec::new(),
+            score: G::Score::default(),
+            phase: Phase::Playing,
+        };
+        game.start_hand()?;
+
+        Ok(game)
+    }
+
+    /// Shuffles a new deck and deals it, starting from the player after the
+    /// dealer, who also leads the first trick. If the rules require it, the
+    /// auction starts, otherwise the hand is set up for playing.
+    fn start_hand(&mut self) -> Result<(), ShuftError> {
+        let mut deck = self.rules.deck();
+        deck.shuffle_with(&mut self.rng);
+
+        let players = self.players.len();
+        let mut first = self.dealer;
+        first.inc();
+        let dealer = self.rules.dealer(players);
+        let deal = dealer.deal(deck, &mut self.players, first)?;
+
+        self.stock = self.rules.stock(&deal, players);
+        let stock = self.stock.as_ref().map_or(0, Stock::len);
+        if !stock.is_multiple_of(players) {
+            return Err(ShuftError::NotEnoughCards {
+                needed: stock.next_multiple_of(players),
+                available: stock,
+            });
+        }
+
+        let tricks = dealer.pattern().cards_per_player() + stock / players;
+        self.hand = OngoingHand::with_tricks(first, tricks);
+
+        self.auction = self.rules.auction(self.hands.len(), self.dealer);
+        if self.auction.is_some() {
+            self.deal = Some(deal);
+            self.phase = Phase::Bidding;
+        } else {
+            self.start_play(&deal, None);
+        }
+
+        Ok(())
+    }
+
+    /// Sets up the context of the hand, with the contract won in the
+    /// auction, if any. If the rules require it, the exchange of cards
+    /// starts.
+    fn start_play(&mut self, deal: &Deal<G::CardType>, contract: Option<G::Contract>) {
+        let stock = self.stock.as_ref().map_or(0, Stock::len);
+        let mut context = self.rules.hand_context(deal, contract).with_stock(stock);
+        if let Some(contract) = contract {
+            context = context.with_contract(contract);
+        }
+        self.hand = mem::take(&mut self.hand).with_context(context);
+
+        let pattern = self
+            .rules
+            .exchange_pattern(self.hands.len(), self.players.len());
+        if pattern == ExchangePattern::Hold {
+            self.exchange = None;
+            self.phase = Phase::Playing;
+        } else {
+            let talon = deal.talon().iter().copied().collect();
+            self.exchange = Some(Exchange::new(pattern).with_talon(talon));
+            self.phase = Phase::Exchanging;
+        }
+    }
+
+    /// Plays `card` for the player whose turn it is. When the trick is
+    /// complete, the players draw from the stock, if any, and its taker leads
+    /// the next one; when the hand is complete, it's scored and, unless the
+    /// game is over, the next hand is dealt.
+    ///
+    /// # Errors
+    ///
+    /// Fails if the game is completed, if the players are still bidding or
+    /// exchanging cards, if the card can't be played (see `OngoingTrick::play`), if the
+    /// players can't draw from the stock (see `Stock::refill`) or if the next
+    /// hand can't be dealt. In every case the game isn't modified.
+    pub fn apply(&mut self, card: G::CardType) -> Result<(), ShuftError> {
+        match self.phase {
+            Phase::Completed => return Err(ShuftError::GameCompleted),
+            Phase::Bidding => return Err(ShuftError::IncompleteAuction),
+            Phase::Exchanging => return Err(ShuftError::IncompleteExchange),
+            Phase::Playing => (),
+        }
+
+        let snapshot = self.snapshot();
+        let result = self.advance(card);
+        if result.is_err() {
+            self.restore(snapshot);
+        }
+
+        result
+    }
+
+    /// Plays `card`, then moves the game forward: the players draw from the
+    /// stock, the hand is scored and the next one is dealt. It can fail after
+    /// changing the game, which is then restored by `apply`.
+    fn advance(&mut self, card: G::CardType) -> Result<(), ShuftError> {
+        let seat = self.current_player().ok_or(ShuftError::HandComplete)?;
+        let index = self.hand.index();
+        self.hand.play(&mut self.players[*seat], card)?;
+        if let (Some(stock), Some(taker)) = (&mut self.stock, self.hand.leader(index + 1)) {
+            stock.refill(&mut self.players, taker)?;
+            let context = self.hand.context().with_stock(stock.len());
+            self.hand = mem::take(&mut self.hand).with_context(context);
+        }
+        if !self.hand.is_complete() {
+            return Ok(());
+        }
+
+        let hand = mem::take(&mut self.hand).finish()?;
+        self.rules.score_hand(&hand, &mut self.score);
+        self.hands.push(hand);
+        if self.rules.is_completed(&self.score) {
+            self.phase = Phase::Completed;
+            return Ok(());
+        }
+
+        self.dealer.inc();
+        self.start_hand()
+    }
+
+    /// Saves the parts of the game that a move can change.
+    fn snapshot(&self) -> Snapshot<G> {
+        Snapshot {
+            rng: self.rng.clone(),
+            players: self.players.clone(),
+            dealer: self.dealer,
+            hand: self.hand.clone(),
+            deal: self.deal.clone(),
+            auction: self.auction.clone(),
+            exchange: self.exchange,
+            stock: self.stock.clone(),
+            hands: self.hands.len(),
+            score: self.score.clone(),
+            phase: self.phase,
+        }
+    }
+
+    /// Brings the game back to the state saved by `snapshot`.
+    fn restore(&mut self, snapshot: Snapshot<G>) {
+        self.rng = snapshot.rng;
+        self.players = snapshot.players;
+        self.dealer = snapshot.dealer;
+        self.hand = snapshot.hand;
+        self.deal = snapshot.deal;
+        self.auction = snapshot.auction;
+        self.exchange = snapshot.exchange;
+        self.stock = snapshot.stock;
+        self.hands.truncate(snapshot.hands);
+        self.score = snapshot.score;
+        self.phase = snapshot.phase;
+    }
+
+    /// Selects the cards `seat` gives away in the exchange. Players can select
+    /// their cards in any order; once every player did it, the cards are
+    /// exchanged and the first trick starts. The cards discarded by a player
+    /// taking the talon are set aside for the rest of the hand.
+    ///
+    /// # Errors
+    ///
+    /// Fails if there's no exchange in progress, if the player doesn't exist
+    /// or if the cards can't be selected, see `Exchange::select`.
+    pub fn exchange(
+        &mut self,
+        seat: PlayerId<G>,
+        cards: CardSet<G::CardType>,
+    ) -> Result<(), ShuftError> {
+        let exchange = self.exchange.as_mut().ok_or(ExchangeError::NoExchange)?;
+        let player = self.players.get(*seat).ok_or(ShuftError::InvalidPlayerId {
+            id: *seat,
+            players: self.players.len(),
+        })?;
+        exchange.select(player, cards)?;
+
+        if exchange.is_complete(self.players.len()) {
+            (*exchange).apply(&mut self.players)?;
+            self.exchange = None;
+            self.phase = Phase::Playing;
+        }
+
+        Ok(())
+    }
+
+    /// The auction of the current hand, if the rules have one. It's over
+    /// once the game is past `Phase::Bidding`.
+    pub fn current_auction(&self) -> Option<&Auction<G>> {
+        self.auction.as_ref()
+    }
+
+    /// The exchange in progress, if the game is in `Phase::Exchanging`.
+    pub fn current_exchange(&self) -> Option<&Exchange<G>> {
+        self.exchange.as_ref()
+    }
+
+    /// The cards the player whose turn it is can play. It's empty when the
+    /// game is completed or while players are bidding or exchanging cards.
+    pub fn legal_moves(&self) -> CardSet<G::CardType> {
+        match self.current_trick() {
+            Some(trick) => G::legal_moves(self.players[*trick.next_to_play()].hand(), trick),
+            None => CardSet::new(),
+        }
+    }
+
+    /// The player whose turn it is to play a card, or None if the game is
+    /// completed or players are bidding or exchanging cards.
+    pub fn current_player(&self) -> Option<PlayerId<G>> {
+        self.current_trick().map(OngoingTrick::next_to_play)
+    }
+
+    /// The current phase of the game.
+    pub fn phase(&self) -> Phase {
+        self.phase
+    }
+
+    /// The rules of the game.
+    pub fn rules(&self) -> &G {
+        &self.rules
+    }
+
+    /// The players, sorted by `PlayerId`.
+    pub fn players(&self) -> &[Player<G>] {
+        &self.players
+    }
+
+    /// The player who dealt the current hand.
+    pub fn dealer(&self) -> PlayerId<G> {
+        self.dealer
+    }
+
+    /// The player who leads the current trick: the player after the dealer
+    /// for the first trick of a hand, the taker of the previous trick
+    /// otherwise. It's None if no trick is being played.
+    pub fn leader(&self) -> Option<PlayerId<G>> {
+        self.current_trick().map(OngoingTrick::first_to_play)
+    }
+
+    /// The trick being played, or None if the game is completed or players
+    /// are bidding or exchanging cards.
+    pub fn current_trick(&self) -> Option<&OngoingTrick<G>> {
+        match self.phase {
+            Phase::Playing => self.hand.current_trick(),
+            Phase::Bidding | Phase::Exchanging | Phase::Completed => None,
+        }
+    }
+
+    /// The stock of the current hand, if the players draw from one. Its face
+    /// up card and the cards drawn after the last trick can be shown to
+    /// every player.
+    pub fn stock(&self) -> Option<&Stock<G::CardType>> {
+        self.stock.as_ref()
+    }
+
+    /// The hand being played, with the tricks completed so far.
+    pub fn current_hand(&self) -> &OngoingHand<G> {
+        &self.hand
+    }
+
+    /// The hands that have been completed.
+    pub fn hands(&self) -> &[Hand<G>] {
+        &self.hands
+    }
+
+    /// The score accumulated in the completed hands.
+    pub fn score(&self) -> &G::Score {
+        &self.score
+    }
+}
+
+impl<G: GameRules + Declarations> Game<G> {
+    /// Declares the combination made of `cards`, held by the player whose
+    /// turn it is, which must be their first turn of the hand, see
+    /// `OngoingHand::declare`.
+    ///
+    /// # Errors
+    ///
+    /// Fails if the game is completed, if the players are still bidding or
+    /// exchanging cards or if the combination can't be declared.
+    pub fn declare(&mut self, cards: CardSet<G::CardType>) -> Result<(), ShuftError> {
+        match self.phase {
+            Phase::Completed => return Err(ShuftError::GameCompleted),
+            Phase::Bidding => return Err(ShuftError::IncompleteAuction),
+            Phase::Exchanging => return Err(ShuftError::IncompleteExchange),
+            Phase::Playing => (),
+        }
+
+        let seat = self.current_player().ok_or(ShuftError::HandComplete)?;
+        self.hand.declare(&self.players[*seat], cards)
+    }
+}
+
+impl<G: GameRules + AuctionRules> Game<G> {
+    /// Makes `call` for the player whose turn it is in the auction. When the
+    /// auction is over, the hand is played with the contract won, or, if
+    /// every player passed, the next dealer deals a new hand.
+    ///
+    /// # Errors
+    ///
+    /// Fails if the game is completed, if there's no auction in progress, if
+    /// the call can't be made (see `Auction::call`) or if the new hand can't
+    /// be dealt. In every case the game isn't modified.
+    pub fn call(&mut self, call: Call<G::Bid>) -> Result<(), ShuftError> {
+        if self.phase == Phase::Completed {
+            return Err(ShuftError::GameCompleted);
+        }
+
+        let snapshot = self.snapshot();
+        let result = self.bid(call);
+        if result.is_err() {
+            self.restore(snapshot);
+        }
+
+        result
+    }
+
+    /// Makes `call`, then moves the game forward when the auction is over.
+    /// It can fail after changing the game, which is then restored by `call`.
+    fn bid(&mut self, call: Call<G::Bid>) -> Result<(), ShuftError> {
+        let auction = self.auction.as_mut().ok_or(CallError::AuctionComplete)?;
+        let seat = auction.next_to_call().ok_or(CallError::AuctionComplete)?;
+        auction.call(seat, call)?;
+        if !auction.is_complete() {
+            return Ok(());
+        }
+
+        let deal = self.deal.take().ok_or(ShuftError::IncompleteAuction)?;
+        match auction.clone().finish()? {
+            Some(contract) => self.start_play(&deal, Some(contract)),
+            None => {
+                for player in &mut self.players {
+                    *player = Player::new(player.id());
+                }
+                self.dealer.inc();
+                self.start_hand()?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// The parts of a `Game` that a move can change, saved before making it so
+/// that a failed move leaves the game unchanged. Completed hands are never
+/// changed, so only their number is saved.
+struct Snapshot<G: GameRules> {
+    rng: ChaCha8Rng,
+    players: Vec<Player<G>>,
+    dealer: PlayerId<G>,
+    hand: OngoingHand<G>,
+    deal: Option<Deal<G::CardType>>,
+    auction: Option<Auction<G>>,
+    exchange: Option<Exchange<G>>,
+    stock: Option<Stock<G::CardType>>,
+    hands: usize,
+    score: G::Score,
+    phase: Phase,
+}
+
+#[cfg(feature = "serde")]
+mod serde_impls {
+    use rand_chacha::ChaCha8Rng;
+    use serde::{
+        de::Error as _, ser::SerializeStruct, Deserialize, Deserializer, Serialize, Serializer,
+    };
+
+    use super::{Game, GameRules, Phase};
+    use crate::common::{
+        auction::Auction,
+        dealer::Deal,
+        exchange::Exchange,
+        hands::{Hand, OngoingHand, Player, PlayerId, TrickTakingGame},
+        stock::Stock,
+    };
+
+    impl<G> Serialize for Game<G>
+    where
+        G: GameRules + Serialize,
+        G::CardType: Serialize,
+        G::Contract: Serialize,
+        G::Bid: Serialize,
+        G::Score: Serialize,
+    {
+        /// The random number generator is stored with its state, so that the
+        /// next hands are dealt like in the original game. The deal, the
+        /// auction, the exchange and the stock are only stored when the hand
+        /// has them.
+        fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+            let mut state = serializer.serialize_struct("Game", 12)?;
+            state.serialize_field("rules", &self.rules)?;
+            state.serialize_field("rng", &self.rng)?;
+            state.serialize_field("players", &self.players)?;
+            state.serialize_field("dealer", &self.dealer)?;
+            state.serialize_field("hand", &self.hand)?;
+            match &self.deal {
+                Some(deal) => state.serialize_field("deal", deal)?,
+                None => state.skip_field("deal")?,
+            }
+            match &self.auction {
+                Some(auction) => state.serialize_field("auction", auction)?,
+                None => state.skip_field("auction")?,
+            }
+            match &self.exchange {
+                Some(exchange) => state.serialize_field("exchange", exchange)?,
+                None => state.skip_field("exchange")?,
+            }
+            match &self.stock {
+                Some(stock) => state.serialize_field("stock", stock)?,
+                None => state.skip_field("stock")?,
+            }
+            state.serialize_field("hands", &self.hands)?;
+            state.serialize_field("score", &self.score)?;
+            state.serialize_field("phase", &self.phase)?;
+            state.end()
+        }
+    }
+
+    #[derive(Deserialize)]
+    #[serde(rename = "Game")]
+    struct GameRepr<R, P, O, D, A, E, K, H, S> {
+        rules: R,
+        rng: ChaCha8Rng,
+        players: Vec<P>,
+        dealer: usize,
+        hand: O,
+        deal: Option<D>,
+        auction: Option<A>,
+        exchange: Option<E>,
+        stock: Option<K>,
+        hands: Vec<H>,
+        score: S,
+        phase: Phase,
+    }
+
+    type Repr<G> = GameRepr<
+        G,
+        Player<G>,
+        OngoingHand<G>,
+        Deal<<G as TrickTakingGame>::CardType>,
+        Auction<G>,
+        Exchange<G>,
+        Stock<<G as TrickTakingGame>::CardType>,
+        Hand<G>,
+        <G as GameRules>::Score,
+    >;
+
+    impl<'de, G> Deserialize<'de> for Game<G>
+    where
+        G: GameRules + Deserialize<'de>,
+        G::CardType: Deserialize<'de>,
+        G::Contract: Deserialize<'de>,
+        G::Bid: Deserialize<'de>,
+        G::Score: Deserialize<'de>,
+    {
+        fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+            let GameRepr {
+                rules,
+                rng,
+                players,
+                dealer,
+                hand,
+                deal,
+                auction,
+                exchange,
+                stock,
+                hands,
+                score,
+                phase,
+            } = Repr::<G>::deserialize(deserializer)?;
+            if !G::supports(players.len())
+                || players
+                    .iter()
+                    .enumerate()
+                    .any(|(i, p)| *p.id() != i || p.id().players() != players.len())
+            {
+                return Err(D::Error::custom(
+                    "the players must be sorted by id, for a number of players supported by the game",
+                ));
+            }
+            let dealer = PlayerId::with_players(dealer, players.len()).ok_or_else(|| {
+                D::Error::custom(format!(
+                    "invalid player id {} for a game with {} players",
+                    dealer,
+                    players.len()
+                ))
+            })?;
+            if (phase == Phase::Bidding) != deal.is_some() || auction.is_none() && deal.is_some() {
+                return Err(D::Error::custom(
+                    "the deal and the auction must be stored while the players are bidding, and the deal only then",
+                ));
+            }
+            if (phase == Phase::Exchanging) != exchange.is_some() {
+                return Err(D::Error::custom(
+                    "the exchange must be stored while the players are exchanging cards, and only then",
+                ));
+            }
+
+            Ok(Game {
+                rules,
+                rng,
+                players,
+                dealer,
+                hand,
+                deal,
+                auction,
+                exchange,
+                stock,
+                hands,
+                score,
+                phase,
+            })
+        }
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use std::{cell::Cell, cmp::Ordering};
+
+    use crate::{
+        common::{
+            auction::{Auction, AuctionRules, Call, CallError, Doubling},
+            cards::{CardSet, Deck, Ranked, Suit},
+            dealer::{Deal, DealPattern, Dealer},
+            exchange::{ExchangeError, ExchangePattern},
+            hands::{
+                highest_trump_or_led, Hand, HandContext, OngoingTrick, PlayerId, TrickStrength,
+                TrickTakingGame,
+            },
+            stock::Stock,
+        },
+        error::ShuftError,
+        tressette::TressetteCard,
+    };
+
+    use super::{Game, GameRules, Phase};
+
+    /// A game played with the tressette deck, where the highest rank of the
+    /// led suit takes, each player scores the number of tricks they take and
+    /// the game ends after two hands. Before playing, players pass the given
+    /// number of cards, like in hearts. With a stock, players receive 3 cards
+    /// and draw the other ones, like in briscola, and they don't have to
+    /// follow suit until the stock is exhausted. With `full_stock`, the stock
+    /// is a whole new deck, so that players draw cards they already hold.
+    /// With an auction, players bid the number of tricks they will take, and
+    /// hearts are trumps when someone bids. The deck of the hand with index
+    /// `broken_hand` lacks a card, so that it can't be dealt.
+    #[derive(Debug, Clone, Default)]
+    #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+    struct CountTricks {
+        pass: usize,
+        auction: bool,
+        stock: bool,
+        full_stock: bool,
+        broken_hand: Option<usize>,
+        decks: Cell<usize>,
+    }
+
+    impl TrickTakingGame for CountTricks {
+        type CardType = TressetteCard;
+        type Contract = (usize, u8);
+        type Bid = u8;
+
+        const PLAYERS: usize = 4;
+
+        const TRICKS: usize = 10;
+
+        fn determine_taker(
+            cards: &[TressetteCard],
+            first_to_play: PlayerId<Self>,
+            context: &HandContext<Self>,
+        ) -> PlayerId<Self> {
+            highest_trump_or_led(cards, first_to_play, context)
+        }
+
+        fn legal_moves(
+            hand: &CardSet<TressetteCard>,
+            trick: &OngoingTrick<Self>,
+        ) -> CardSet<TressetteCard> {
+            if trick.context().stock() > 0 {
+                *hand
+            } else {
+                trick.context().follow(hand, trick.led())
+            }
+        }
+    }
+
+    impl TrickStrength for CountTricks {
+        /// Cards following the led card are ranked from the ace to the king.
+        fn compare(
+            led: &TressetteCard,
+            a: &TressetteCard,
+            b: &TressetteCard,
+            _context: &HandContext<Self>,
+        ) -> Ordering {
+            let strength = |c: &TressetteCard| (c.suit() == led.suit()).then_some(c.rank());
+            strength(a).cmp(&strength(b))
+        }
+    }
+
+    impl AuctionRules for CountTricks {
+        fn is_sufficient(bid: &u8, highest: Option<&u8>) -> bool {
+            highest.map_or(*bid >= 3, |highest| bid > highest)
+        }
+
+        fn contract(
+            bidder: PlayerId<Self>,
+            bid: u8,
+            _doubling: Doubling,
+            _calls: &[(PlayerId<Self>, Call<u8>)],
+        ) -> Self::Contract {
+            (*bidder, bid)
+        }
+    }
+
+    impl GameRules for CountTricks {
+        type Score = (usize, [usize; 4]);
+
+        fn deck(&self) -> Deck<TressetteCard> {
+            let mut deck = Deck::tressette();
+            if self.broken_hand == Some(self.decks.get()) {
+                deck.pop();
+            }
+            self.decks.set(self.decks.get() + 1);
+            deck
+        }
+
+        fn dealer(&self, _players: usize) -> Dealer {
+            if self.stock {
+                Dealer::new(DealPattern::packets(3, 1))
+            } else {
+                Dealer::new(DealPattern::packets(5, 2))
+            }
+        }
+
+        fn auction(&self, _hand: usize, dealer: PlayerId<Self>) -> Option<Auction<Self>> {
+            self.auction.then(|| Auction::new(dealer))
+        }
+
+        fn hand_context(
+            &self,
+            _deal: &Deal<TressetteCard>,
+            contract: Option<Self::Contract>,
+        ) -> HandContext<Self> {
+            match contract {
+                Some(_) => HandContext::new().with_trump(Suit::Hearts),
+                None => HandContext::new(),
+            }
+        }
+
+        fn stock(
+            &self,
+            deal: &Deal<TressetteCard>,
+            _players: usize,
+        ) -> Option<Stock<TressetteCard>> {
+            let cards = if self.full_stock {
+                Deck::tressette()
+            } else {
+                deal.stock().clone()
+            };
+            self.stock.then(|| Stock::new(cards).turn_up())
+        }
+
+        fn exchange_pattern(&self, hand: usize, players: usize) -> ExchangePattern {
+            ExchangePattern::rotating(hand, self.pass, players)
+        }
+
+        fn score_hand(&self, hand: &Hand<Self>, score: &mut Self::Score) {
+            score.0 += 1;
+            for trick in hand.tricks() {
+                score.1[*trick.taker()] += 1;
+            }
+        }
+
+        fn is_completed(&self, score: &Self::Score) -> bool {
+            score.0 == 2
+        }
+    }
+
+    #[test]
+    fn game_follows_the_flow_of_play() {
+        let mut game = Game::new(CountTricks::default(), 7).unwrap();
+        assert_eq!(*game.dealer(), 0);
+        assert_eq!(game.leader(), PlayerId::new(1));
+        assert!(game.players().iter().all(|p| p.hand().len() == 10));
+
+        while game.phase() == Phase::Playing {
+            let hands = game.hands().len();
+            let tricks = game.current_hand().index();
+            let card = game.legal_moves().iter().next().unwrap();
+            game.apply(card).unwrap();
+
+            if game.phase() == Phase::Completed {
+                break;
+            }
+            if game.hands().len() > hands {
+                // A new hand is dealt by the next player.
+                assert_eq!(*game.dealer(), game.hands().len());
+                assert_eq!(game.leader(), PlayerId::new(game.hands().len() + 1));
+                assert!(game.players().iter().all(|p| p.hand().len() == 10));
+            } else if let Some(trick) = game.current_hand().tricks().get(tricks).copied().flatten()
+            {
+                // The taker of the last trick leads the next one.
+                assert_eq!(game.leader(), Some(trick.taker()));
+            }
+        }
+
+        assert_eq!(game.hands().len(), 2);
+        assert_eq!(game.score().1.iter().sum::<usize>(), 20);
+        assert!(game.legal_moves().is_empty());
+        assert!(game.current_player().is_none());
+        assert_eq!(
+            game.apply(TressetteCard::default()),
+            Err(ShuftError::GameCompleted)
+        );
+    }
+
+    #[test]
+    fn illegal_moves_leave_the_game_unchanged() {
+        let mut game = Game::new(CountTricks::default(), 0).unwrap();
+        let seat = *game.current_player().unwrap();
+        let other = game.players()[(seat + 1) % 4].hand().iter().next().unwrap();
+
+        assert!(game.apply(other).is_err());
+        assert_eq!(game.players()[seat].hand().len(), 10);
+        assert!(game
+            .current_trick()
+            .unwrap()
+            .cards()
+            .iter()
+            .all(Option::is_none));
+    }
+
+    #[test]
+    fn failed_deals_leave_the_game_unchanged() {
+        let rules = CountTricks {
+            broken_hand: Some(1),
+            ..Default::default()
+        };
+        let mut game = Game::new(rules, 2).unwrap();
+        for _ in 0..39 {
+            let card = game.legal_moves().iter().next().unwrap();
+            game.apply(card).unwrap();
+        }
+
+        // The last card completes the hand, but the next one can't be dealt.
+        let seat = *game.current_player().unwrap();
+        let card = game.legal_moves().iter().next().unwrap();
+        assert_eq!(
+            game.apply(card),
+            Err(ShuftError::NotEnoughCards {
+                needed: 40,
+                available: 39
+            })
+        );
+        assert!(game.hands().is_empty());
+        assert_eq!(game.score().0, 0);
+        assert_eq!(*game.dealer(), 0);
+        assert_eq!(game.current_hand().index(), 9);
+        assert!(game.players()[seat].hand().contains(card));
+
+        // The next deck is complete.
+        game.apply(card).unwrap();
+        assert_eq!(game.hands().len(), 1);
+        assert_eq!(*game.dealer(), 1);
+    }
+
+    #[test]
+    fn failed_draws_leave_the_game_unchanged() {
+        let rules = CountTricks {
+            stock: true,
+            full_stock: true,
+            ..Default::default()
+        };
+        let mut game = Game::new(rules, 1).unwrap();
+        let error = loop {
+            assert!(game.hands().is_empty());
+            let hands: Vec<_> = game.players().iter().map(|p| *p.hand()).collect();
+            let trick = game.current_trick().unwrap().cards().to_vec();
+            let stock = game.stock().unwrap().len();
+            let card = game.legal_moves().iter().next().unwrap();
+            let Err(error) = game.apply(card) else {
+                continue;
+            };
+
+            // The last card of the trick isn't played if a player can't draw.
+            assert!(game.players().iter().map(|p| *p.hand()).eq(hands));
+            assert_eq!(game.current_trick().unwrap().cards(), trick);
+            assert_eq!(game.stock().unwrap().len(), stock);
+            break error;
+        };
+        assert!(matches!(error, ShuftError::DuplicateCard(_)));
+    }
+
+    #[test]
+    fn same_seed_same_game() {
+        let first = Game::new(CountTricks::default(), 3).unwrap();
+        let second = Game::new(CountTricks::default(), 3).unwrap();
+        for (a, b) in first.players().iter().zip(second.players()) {
+            assert_eq!(a.hand(), b.hand());
+        }
+    }
+
+    #[test]
+    fn cards_are_exchanged_before_playing() {
+        let mut game = Game::new(
+            CountTricks {
+                pass: 3,
+                ..Default::default()
+            },
+            5,
+        )
+        .unwrap();
+        assert_eq!(game.phase(), Phase::Exchanging);
+        assert!(game.current_player().is_none());
+        assert!(game.legal_moves().is_empty());
+        let card = game.players()[1].hand().iter().next().unwrap();
+        assert_eq!(game.apply(card), Err(ShuftError::IncompleteExchange));
+
+        let before: Vec<_> = game.players().iter().map(|p| *p.hand()).collect();
+        let passed: Vec<_> = before
+            .iter()
+            .map(|hand| hand.iter().take(3).collect())
+            .collect();
+        for (seat, &cards) in passed.iter().enumerate() {
+            assert_eq!(game.phase(), Phase::Exchanging);
+            game.exchange(PlayerId::new(seat).unwrap(), cards).unwrap();
+        }
+
+        // The first hand passes to the left.
+        assert_eq!(game.phase(), Phase::Playing);
+        assert!(game.current_exchange().is_none());
+        for (seat, player) in game.players().iter().enumerate() {
+            let from_right = passed[(seat + 3) % 4];
+            assert_eq!(player.hand().len(), 10);
+            assert!(from_right.iter().all(|c| player.hand().contains(c)));
+            assert!(passed[seat].iter().all(|c| !player.hand().contains(c)));
+        }
+        assert_eq!(
+            game.exchange(PlayerId::new(0).unwrap(), passed[0]),
+            Err(ShuftError::IllegalExchange(ExchangeError::NoExchange))
+        );
+    }
+
+    #[test]
+    fn players_draw_until_the_stock_is_exhausted() {
+        let rules = CountTricks {
+            stock: true,
+            ..Default::default()
+        };
+        let mut game = Game::new(rules, 11).unwrap();
+        let face_up = game.stock().unwrap().face_up().unwrap();
+        assert_eq!(game.stock().unwrap().len(), 28);
+        assert_eq!(game.current_hand().tricks().len(), 10);
+        assert_eq!(game.current_hand().context().stock(), 28);
+
+        while !game.stock().unwrap().is_empty() {
+            let seat = *game.current_player().unwrap();
+            assert_eq!(game.legal_moves(), *game.players()[seat].hand());
+            for _ in 0..4 {
+                let card = game.legal_moves().iter().next().unwrap();
+                game.apply(card).unwrap();
+            }
+
+            // The taker draws first and leads the next trick.
+            let taker = *game.leader().unwrap();
+            let drawn = game.stock().unwrap().drawn(taker).unwrap();
+            assert!(game.players()[taker].hand().contains(drawn));
+            assert!(game.players().iter().all(|p| p.hand().len() == 3));
+        }
+
+        // The face up card is drawn last, by the player before the taker.
+        let last = (*game.leader().unwrap() + 3) % 4;
+        assert!(game.players()[last].hand().contains(face_up));
+        assert_eq!(game.current_hand().context().stock(), 0);
+
+        // Players must follow suit from now on.
+        let led = game.legal_moves().iter().next().unwrap();
+        game.apply(led).unwrap();
+        let seat = *game.current_player().unwrap();
+        let context = game.current_hand().context();
+        assert_eq!(
+            game.legal_moves(),
+            context.follow(game.players()[seat].hand(), Some(led))
+        );
+    }
+
+    #[test]
+    fn hands_are_bid_before_playing() {
+        let rules = CountTricks {
+            auction: true,
+            ..Default::default()
+        };
+        let mut game = Game::new(rules, 4).unwrap();
+        assert_eq!(game.phase(), Phase::Bidding);
+        assert!(game.current_player().is_none());
+        assert!(game.legal_moves().is_empty());
+        let card = game.players()[1].hand().iter().next().unwrap();
+        assert_eq!(game.apply(card), Err(ShuftError::IncompleteAuction));
+        assert_eq!(
+            game.call(Call::Bid(2)),
+            Err(ShuftError::IllegalCall(CallError::InsufficientBid))
+        );
+
+        for call in [Call::Bid(3), Call::Bid(5), Call::Pass, Call::Pass] {
+            game.call(call).unwrap();
+        }
+        assert_eq!(game.phase(), Phase::Bidding);
+        game.call(Call::Pass).unwrap();
+
+        // The contract is known while playing, and the first trick is led by
+        // the player after the dealer.
+        assert_eq!(game.phase(), Phase::Playing);
+        assert_eq!(game.current_auction().unwrap().calls().len(), 5);
+        assert_eq!(game.current_hand().context().contract(), Some((2, 5)));
+        assert_eq!(game.current_hand().context().trump(), Some(Suit::Hearts));
+        assert_eq!(game.leader(), PlayerId::new(1));
+        assert_eq!(
+            game.call(Call::Pass),
+            Err(ShuftError::IllegalCall(CallError::AuctionComplete))
+        );
+
+        for _ in 0..40 {
+            let card = game.legal_moves().iter().next().unwrap();
+            game.apply(card).unwrap();
+        }
+        assert_eq!(game.hands()[0].context().contract(), Some((2, 5)));
+        assert_eq!(game.phase(), Phase::Bidding);
+        assert_eq!(game.current_auction().unwrap().dealer(), game.dealer());
+    }
+
+    #[test]
+    fn passed_hands_are_dealt_again() {
+        let rules = CountTricks {
+            auction: true,
+            ..Default::default()
+        };
+        let mut game = Game::new(rules, 9).unwrap();
+        let hands: Vec<_> = game.players().iter().map(|p| *p.hand()).collect();
+        for _ in 0..4 {
+            game.call(Call::Pass).unwrap();
+        }
+
+        assert_eq!(game.phase(), Phase::Bidding);
+        assert_eq!(*game.dealer(), 1);
+        assert!(game.hands().is_empty());
+        assert!(game.current_auction().unwrap().calls().is_empty());
+        assert!(game.players().iter().all(|p| p.hand().len() == 10));
+        assert!(!game.players().iter().map(|p| *p.hand()).eq(hands));
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn saved_games_go_on_like_the_original() {
+        let rules = CountTricks {
+            pass: 1,
+            auction: true,
+            ..Default::default()
+        };
+        let mut game = Game::new(rules, 6).unwrap();
+        game.call(Call::Bid(4)).unwrap();
+
+        let json = serde_json::to_string(&game).unwrap();
+        let mut deserialized: Game<CountTricks> = serde_json::from_str(&json).unwrap();
+        assert_eq!(deserialized.phase(), Phase::Bidding);
+        assert_eq!(deserialized.current_auction().unwrap().calls().len(), 1);
+
+        while game.phase() != Phase::Completed {
+            match game.phase() {
+                Phase::Bidding => {
+                    let call = match game.current_auction().unwrap().highest_bid() {
+                        Some(_) => Call::Pass,
+                        None => Call::Bid(3),
+                    };
+                    game.call(call).unwrap();
+                    deserialized.call(call).unwrap();
+                }
+                Phase::Exchanging => {
+                    for seat in 0..4 {
+                        let seat = PlayerId::new(seat).unwrap();
+                        let cards = game.players()[*seat].hand().iter().take(1).collect();
+                        game.exchange(seat, cards).unwrap();
+                        deserialized.exchange(seat, cards).unwrap();
+                    }
+                }
+                Phase::Playing | Phase::Completed => {
+                    let card = game.legal_moves().iter().next().unwrap();
+                    game.apply(card).unwrap();
+                    deserialized.apply(card).unwrap();
+                }
+            }
+        }
+        assert_eq!(deserialized.score(), game.score());
+        assert_eq!(
+            serde_json::to_string(&deserialized).unwrap(),
+            serde_json::to_string(&game).unwrap()
+        );
+    }
+}
@@ -1,50 +1,461 @@
-use std::{fmt::Display, ops::Deref};
+use std::{
+    cmp::Ordering,
+    error::Error,
+    fmt::{Debug, Display},
+    hash::Hash,
+    marker::PhantomData,
+    ops::Deref,
+};
 
-use anyhow::bail;
+use super::{
+    cards::{CardSet, Suit, Suited},
+    teams::Teams,
+};
+use crate::error::ShuftError;
 
-use super::cards::Card;
+/// The maximum number of players supported by the types of this module. The
+/// cards of a trick are stored inline, in an array of this size.
+pub const MAX_PLAYERS: usize = 8;
 
 /// Many of the types contained in  this module are generic over certain
 /// constants related to the game. This trait is the summary of these
 /// constraints.
-pub trait TrickTakingGame {
+pub trait TrickTakingGame: Sized {
     /// Define the type of card that's going to be used in this game.
-    type CardType: Card;
+    type CardType: Suited;
+    /// The contract declared before playing a hand, in games with an
+    /// auction, like the level and the strain of bridge. Games without
+    /// contracts use `()`.
+    type Contract: Copy + Debug + PartialEq;
+    /// A bid of the auction played before a hand, like "3 no trump" in
+    /// bridge or the value of the game in skat, see `AuctionRules`. Games
+    /// without an auction use `()`.
+    type Bid: Copy + Debug + PartialEq;
     /// Every game has a fixed number of players defined by the rules of the
-    /// game or, anyway, before starting it.
+    /// game or, anyway, before starting it. This is the default one, used by
+    /// `PlayerId::new` and `OngoingHand::new`. It can't be greater than
+    /// `MAX_PLAYERS`.
     const PLAYERS: usize;
     /// Usually trick taking games have a fixed number of "turns" for each
-    /// player. These "turns" are called tricks
+    /// player. These "turns" are called tricks. This is the number of tricks
+    /// played with the default number of players.
     const TRICKS: usize;
 
+    /// Whether the game can be played by the given number of players. Games
+    /// that support more than one number of players, chosen when the game is
+    /// created, should override it. By default, only `PLAYERS` is supported.
+    fn supports(players: usize) -> bool {
+        players == Self::PLAYERS
+    }
+
+    /// How the given number of players are grouped into teams. By default,
+    /// every player plays for themselves. Games where the teams change from
+    /// hand to hand, like the ones with a called partner, return the teams
+    /// they start with.
+    fn teams(_players: usize) -> Teams {
+        Teams::Individual
+    }
+
     /// Every trick taking game has some logic to determine the winner (or
     /// taker) of the trick. The taker is generally determined by the cards that
     /// have been played and it can depend by the order in which the players
-    /// played their cards.
+    /// played their cards. `cards` contains one card for each player of the
+    /// trick, indexed by `PlayerId`, while `context` contains the trumps and
+    /// the contract of the hand. Games with trumps can use
+    /// `highest_trump_or_led`, which ranks cards with `TrickStrength`.
     fn determine_taker(
-        cards: &[Self::CardType; Self::PLAYERS],
-        first_to_play: PlayerId<{ Self::PLAYERS }>,
-    ) -> PlayerId<{ Self::PLAYERS }>;
+        cards: &[Self::CardType],
+        first_to_play: PlayerId<Self>,
+        context: &HandContext<Self>,
+    ) -> PlayerId<Self>;
+
+    /// The cards of `hand` that can be played in `trick` by the player whose
+    /// turn it is. By default, players must follow the first card of the
+    /// trick if they can, otherwise they can play any card. Trumps follow
+    /// each other, see `HandContext::follow`.
+    fn legal_moves(
+        hand: &CardSet<Self::CardType>,
+        trick: &OngoingTrick<Self>,
+    ) -> CardSet<Self::CardType> {
+        trick.context().follow(hand, trick.led())
+    }
+
+    /// Whether `card` can be played from `hand` in `trick`. It's consistent
+    /// with `legal_moves` by default.
+    fn is_legal(
+        hand: &CardSet<Self::CardType>,
+        trick: &OngoingTrick<Self>,
+        card: Self::CardType,
+    ) -> bool {
+        Self::legal_moves(hand, trick).contains(card)
+    }
+}
+
+/// The cards of `hand` that can be played when the trick has been led with
+/// the given suit, if players must follow suit: the cards of that suit, or the
+/// whole hand if there are none of them or no card has been played yet.
+pub fn follow_suit<C: Suited>(hand: &CardSet<C>, led: Option<Suit>) -> CardSet<C> {
+    if let Some(led) = led {
+        let same_suit = hand.of_suit(led);
+        if !same_suit.is_empty() {
+            return same_suit;
+        }
+    }
+
+    *hand
+}
+
+/// The state of a hand that changes how its tricks are played and taken,
+/// decided before the first trick: the trump suit, the cards that are always
+/// trumps, like the bowers of euchre or the jacks of skat, and the contract
+/// declared in the auction. It also tracks the cards left in the stock, for
+/// games where players draw after every trick and the rules change once it's
+/// exhausted. By default there are no trumps, no contract and no stock.
+///
+/// # Examples
+///
+/// ```
+/// use shuftlib::common::{cards::{CardSet, ItalianRank, Suit}, hands::HandContext};
+/// use shuftlib::tressette::{TressetteCard, TressetteRules};
+///
+/// let two = TressetteCard::new(ItalianRank::Two, Suit::Clubs);
+/// let context = HandContext::<TressetteRules>::new()
+///     .with_trump(Suit::Hearts)
+///     .with_permanent_trumps([two].into_iter().collect());
+///
+/// assert!(context.is_trump(two));
+/// assert!(context.is_trump(TressetteCard::new(ItalianRank::Four, Suit::Hearts)));
+/// assert!(!context.is_trump(TressetteCard::new(ItalianRank::Three, Suit::Clubs)));
+/// ```
+pub struct HandContext<G>
+where
+    G: TrickTakingGame,
+{
+    trump: Option<Suit>,
+    permanent_trumps: CardSet<G::CardType>,
+    contract: Option<G::Contract>,
+    stock: usize,
+}
+
+impl<G> HandContext<G>
+where
+    G: TrickTakingGame,
+{
+    /// Creates a context without trumps and without a contract.
+    pub fn new() -> Self {
+        Self {
+            trump: None,
+            permanent_trumps: CardSet::new(),
+            contract: None,
+            stock: 0,
+        }
+    }
+
+    /// Sets the trump suit.
+    pub fn with_trump(mut self, suit: Suit) -> Self {
+        self.trump = Some(suit);
+        self
+    }
+
+    /// Sets the cards that are trumps whatever their suit. They beat every
+    /// card of the trump suit.
+    pub fn with_permanent_trumps(mut self, cards: CardSet<G::CardType>) -> Self {
+        self.permanent_trumps = cards;
+        self
+    }
+
+    /// Sets the contract declared for the hand.
+    pub fn with_contract(mut self, contract: G::Contract) -> Self {
+        self.contract = Some(contract);
+        self
+    }
+
+    /// Sets the number of cards left in the stock.
+    pub fn with_stock(mut self, cards: usize) -> Self {
+        self.stock = cards;
+        self
+    }
+
+    /// The trump suit, if any.
+    pub fn trump(&self) -> Option<Suit> {
+        self.trump
+    }
+
+    /// The cards that are trumps whatever their suit.
+    pub fn permanent_trumps(&self) -> CardSet<G::CardType> {
+        self.permanent_trumps
+    }
+
+    /// The contract declared for the hand, if any.
+    pub fn contract(&self) -> Option<G::Contract> {
+        self.contract
+    }
+
+    /// The number of cards left in the stock, which is 0 once it has been
+    /// exhausted or if the hand is played without one.
+    pub fn stock(&self) -> usize {
+        self.stock
+    }
+
+    /// Whether `card` is a trump: a permanent trump or a card of the trump
+    /// suit.
+    pub fn is_trump(&self, card: G::CardType) -> bool {
+        self.permanent_trumps.contains(card) || self.trump == Some(card.suit())
+    }
+
+    /// Whether `card` follows `led`: when a trump is led, every trump follows
+    /// it, otherwise only the cards of the same suit which aren't trumps.
+    pub fn follows(&self, card: G::CardType, led: G::CardType) -> bool {
+        if self.is_trump(led) {
+            self.is_trump(card)
+        } else {
+            !self.is_trump(card) && card.suit() == led.suit()
+        }
+    }
+
+    /// The cards of `hand` that follow `led`, or the whole hand if there are
+    /// none of them or no card has been led yet. Without trumps, it's the
+    /// same as `follow_suit`.
+    pub fn follow(
+        &self,
+        hand: &CardSet<G::CardType>,
+        led: Option<G::CardType>,
+    ) -> CardSet<G::CardType> {
+        if let Some(led) = led {
+            let following: CardSet<_> = hand.iter().filter(|&c| self.follows(c, led)).collect();
+            if !following.is_empty() {
+                return following;
+            }
+        }
+
+        *hand
+    }
+}
+
+impl<G: TrickTakingGame> Default for HandContext<G> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<G: TrickTakingGame> Clone for HandContext<G> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<G: TrickTakingGame> Copy for HandContext<G> {}
+
+impl<G: TrickTakingGame> PartialEq for HandContext<G> {
+    fn eq(&self, other: &Self) -> bool {
+        self.trump == other.trump
+            && self.permanent_trumps == other.permanent_trumps
+            && self.contract == other.contract
+            && self.stock == other.stock
+    }
+}
+
+impl<G: TrickTakingGame> Debug for HandContext<G> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("HandContext")
+            .field("trump", &self.trump)
+            .field("permanent_trumps", &self.permanent_trumps)
+            .field("contract", &self.contract)
+            .field("stock", &self.stock)
+            .finish()
+    }
+}
+
+/// Determines the taker of a trick in the most common way for games with
+/// trumps: the highest permanent trump, if any has been played, otherwise the
+/// highest card of the trump suit, otherwise the highest card following the
+/// led card. Cards of the same kind are compared with `TrickStrength::compare`,
+/// as if the highest one played before had been led, so permanent trumps are
+/// ranked against each other by the game, and between cards that
+/// can't beat each other the first one played wins. Without trumps, the
+/// highest card following the led card takes.
+///
+/// # Examples
+///
+/// ```
+/// use shuftlib::common::{cards::{ItalianRank, Suit}, hands::{highest_trump_or_led, HandContext, PlayerId}};
+/// use shuftlib::tressette::{TressetteCard, TressetteRules};
+///
+/// let cards = [
+///     TressetteCard::new(ItalianRank::King, Suit::Clubs),
+///     TressetteCard::new(ItalianRank::Four, Suit::Hearts),
+///     TressetteCard::new(ItalianRank::Seven, Suit::Clubs),
+///     TressetteCard::new(ItalianRank::Five, Suit::Hearts),
+/// ];
+/// let first_to_play = PlayerId::<TressetteRules>::new(0).unwrap();
+///
+/// let taker = highest_trump_or_led(&cards, first_to_play, &HandContext::new());
+/// assert_eq!(*taker, 0);
+///
+/// let context = HandContext::new().with_trump(Suit::Hearts);
+/// let taker = highest_trump_or_led(&cards, first_to_play, &context);
+/// assert_eq!(*taker, 3);
+/// ```
+pub fn highest_trump_or_led<G>(
+    cards: &[G::CardType],
+    first_to_play: PlayerId<G>,
+    context: &HandContext<G>,
+) -> PlayerId<G>
+where
+    G: TrickStrength,
+{
+    let led = cards[*first_to_play];
+    let kind = |card: &G::CardType| {
+        if context.permanent_trumps().contains(*card) {
+            3
+        } else if context.trump() == Some(card.suit()) {
+            2
+        } else if context.follows(*card, led) {
+            1
+        } else {
+            0
+        }
+    };
+
+    let mut taker = first_to_play;
+    let mut best = led;
+    let mut seat = first_to_play;
+    for _ in 1..first_to_play.players() {
+        seat.inc();
+        let card = cards[*seat];
+        let beats = match kind(&card).cmp(&kind(&best)) {
+            Ordering::Greater => true,
+            Ordering::Equal => G::compare(&best, &card, &best, context) == Ordering::Greater,
+            Ordering::Less => false,
+        };
+        if beats {
+            best = card;
+            taker = seat;
+        }
+    }
+
+    taker
+}
+
+/// The reasons why a card can't be played in a trick.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PlayError {
+    /// The player doesn't hold the card.
+    NotInHand,
+    /// The card doesn't follow the rules of the game, for example because the
+    /// player didn't follow suit while they could.
+    Revoke,
+    /// It's not the turn of the player.
+    WrongTurn {
+        /// The id of the player whose turn it is.
+        expected: usize,
+        /// The id of the player who tried to play.
+        found: usize,
+    },
+    /// Every player already played a card in the trick.
+    TrickComplete,
+}
+
+impl Display for PlayError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            PlayError::NotInHand => write!(f, "the card is not in the hand of the player"),
+            PlayError::Revoke => write!(f, "the card can't be played by the rules of the game"),
+            PlayError::WrongTurn { expected, found } => write!(
+                f,
+                "player {} tried to play, but it's the turn of player {}",
+                found, expected
+            ),
+            PlayError::TrickComplete => write!(f, "every player already played in the trick"),
+        }
+    }
+}
+
+impl Error for PlayError {}
+
+/// Games where players can declare combinations of cards at their first turn
+/// to score points, like the accusi of tressette. A declaration shows the
+/// cards of the combination to every player, and it's kept in the hand, see
+/// `OngoingHand::declare`.
+pub trait Declarations: TrickTakingGame {
+    /// Whether a player holding `hand` can declare the combination made of
+    /// `cards`.
+    fn can_declare(hand: &CardSet<Self::CardType>, cards: &CardSet<Self::CardType>) -> bool;
+}
+
+/// The reasons why a combination of cards can't be declared in a hand.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DeclarationError {
+    /// Combinations can only be declared by a player at their first turn,
+    /// before playing their first card.
+    NotFirstTurn,
+    /// The combination can't be declared with the hand of the player.
+    NotHeld,
+    /// The player already declared the combination.
+    AlreadyDeclared,
+}
+
+impl Display for DeclarationError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            DeclarationError::NotFirstTurn => {
+                write!(f, "combinations can only be declared at the first turn")
+            }
+            DeclarationError::NotHeld => write!(f, "the player doesn't hold the combination"),
+            DeclarationError::AlreadyDeclared => {
+                write!(f, "the player already declared the combination")
+            }
+        }
+    }
+}
+
+impl Error for DeclarationError {}
+
+/// The strength of the cards played in a trick, by the rules of a game. This is
+/// separate from the `Ord` implementation of the card type, which is a total
+/// order used to sort cards and doesn't depend on the game being played.
+pub trait TrickStrength: TrickTakingGame {
+    /// Compares two cards played in the same trick, where `led` is the first
+    /// card of the trick. The greater card beats the other one, while two
+    /// cards are `Ordering::Equal` when neither of them can beat the other,
+    /// for example when they don't follow the leading suit. The context of
+    /// the hand gives the trumps, for games where their ranking depends on
+    /// the trump suit, like the bowers of euchre.
+    fn compare(
+        led: &Self::CardType,
+        a: &Self::CardType,
+        b: &Self::CardType,
+        context: &HandContext<Self>,
+    ) -> Ordering;
 }
 
 /// Represents a player of a game. This type is generic over the type of the
 /// card used for the specific game and over the number of players of such game.
-#[derive(Clone, Default, Debug)]
+/// The cards held by a player are a `CardSet`, so they can't contain the same
+/// card twice.
+#[derive(Default, Debug)]
 pub struct Player<G>
 where
     G: TrickTakingGame,
-    [(); G::PLAYERS]:,
 {
     /// The cards traditionally held in the hand by the player.
-    hand: Vec<G::CardType>,
+    hand: CardSet<G::CardType>,
     /// The ID of this player. This is used to determine their turn to play.
-    id: PlayerId<{ G::PLAYERS }>,
+    id: PlayerId<G>,
+}
+
+impl<G: TrickTakingGame> Clone for Player<G> {
+    fn clone(&self) -> Self {
+        Self {
+            hand: self.hand,
+            id: self.id,
+        }
+    }
 }
 
 impl<G> Player<G>
 where
     G: TrickTakingGame,
-    [(); G::PLAYERS]:,
 {
     /// Adds a card to the hand of the player.
     ///
@@ -54,8 +465,8 @@ where
     /// use shuftlib::common::cards::{ItalianRank, Suit};
     /// use shuftlib::tressette::{TressetteRules, TressetteCard};
     ///
-    /// let player_id = PlayerId::<{TressetteRules::PLAYERS}>::new(0).unwrap();
-    /// let mut player = Player::<TressetteRules>::new(player_id);
+    /// let player_id = PlayerId::<TressetteRules>::new(0).unwrap();
+    /// let mut player = Player::new(player_id);
     /// // Players have no cards when created.
     /// assert_eq!(player.hand().len(), 0);
     ///
@@ -64,7 +475,7 @@ where
     /// assert_eq!(player.hand().len(), 1);
     /// ```
     pub fn give(&mut self, card: G::CardType) {
-        self.hand.push(card);
+        self.hand.insert(card);
     }
 
     /// Removes a card from the hand of the player.
@@ -75,8 +486,8 @@ where
     /// use shuftlib::common::cards::{ItalianRank, Suit};
     /// use shuftlib::tressette::{TressetteRules, TressetteCard};
     ///
-    /// let player_id = PlayerId::<{TressetteRules::PLAYERS}>::new(0).unwrap();
-    /// let mut player = Player::<TressetteRules>::new(player_id);
+    /// let player_id = PlayerId::<TressetteRules>::new(0).unwrap();
+    /// let mut player = Player::new(player_id);
     /// // Players have no cards when created.
     /// assert_eq!(player.hand().len(), 0);
     ///
@@ -88,16 +499,16 @@ where
     /// assert_eq!(player.hand().len(), 0);
     /// ```
     pub fn remove(&mut self, card: G::CardType) {
-        self.hand.retain(|&c| c != card);
+        self.hand.remove(card);
     }
 
     /// Getter for the cards held by this player.
-    pub fn hand(&self) -> &[G::CardType] {
+    pub fn hand(&self) -> &CardSet<G::CardType> {
         &self.hand
     }
 
     /// Getter for the id of this player.
-    pub fn id(&self) -> PlayerId<{ G::PLAYERS }> {
+    pub fn id(&self) -> PlayerId<G> {
         self.id
     }
 
@@ -109,26 +520,31 @@ where
     /// ```
     /// use shuftlib::{common::hands::{Player, PlayerId, TrickTakingGame}, tressette::TressetteRules};
     ///
-    /// let id = PlayerId::<{TressetteRules::PLAYERS}>::new(0).unwrap();
-    /// let player = Player::<TressetteRules>::new(id);
+    /// let id = PlayerId::<TressetteRules>::new(0).unwrap();
+    /// let player = Player::new(id);
     ///
     /// assert_eq!(*player.id(), 0);
     /// assert_eq!(player.hand().len(), 0);
     /// ````
-    pub fn new(id: PlayerId<{ G::PLAYERS }>) -> Self {
+    pub fn new(id: PlayerId<G>) -> Self {
         Self {
             id,
-            hand: Vec::new(),
+            hand: CardSet::new(),
         }
     }
 }
 
-/// A player id can only be in the range 0..N, where N depends on the game being
-/// played and it's the number of players playing that specific game.
-#[derive(Default, Debug, Clone, Copy, Eq, PartialEq, PartialOrd, Ord)]
-pub struct PlayerId<const PLAYERS: usize>(usize);
+/// A player id can only be in the range 0..N, where N is the number of players
+/// of the game `G`. The number of players is stored in the id, so that the
+/// same game can be played by a number of players chosen at runtime (see
+/// `TrickTakingGame::supports`).
+pub struct PlayerId<G> {
+    value: usize,
+    players: usize,
+    game: PhantomData<fn() -> G>,
+}
 
-impl<const PLAYERS: usize> PlayerId<PLAYERS> {
+impl<G: TrickTakingGame> PlayerId<G> {
     /// This method simply increments `self` by 1. Note that `PlayerId` can only
     /// be in the range 0..N, so incrementing `self` when the value is N-1, will
     /// reset its value to 0, since the purpose of this type is to determine the
@@ -137,181 +553,355 @@ impl<const PLAYERS: usize> PlayerId<PLAYERS> {
     ///
     /// # Examples
     /// ```
-    /// #![feature(generic_const_exprs)]
     /// use shuftlib::common::hands::PlayerId;
+    /// use shuftlib::tressette::TressetteRules;
     ///
-    /// let mut player_id: PlayerId<4>= PlayerId::new(0).unwrap();
+    /// let mut player_id = PlayerId::<TressetteRules>::new(0).unwrap();
     /// player_id.inc();
-    /// assert_eq!(player_id, PlayerId::<4>::new(1).unwrap());
+    /// assert_eq!(player_id, PlayerId::new(1).unwrap());
     /// player_id.inc();
     /// player_id.inc();
     /// player_id.inc();
-    /// assert_eq!(player_id, PlayerId::<4>::new(0).unwrap());
+    /// assert_eq!(player_id, PlayerId::new(0).unwrap());
     /// ```
     pub fn inc(&mut self) {
-        if self.0 < PLAYERS - 1 {
-            self.0 += 1;
+        if self.value < self.players - 1 {
+            self.value += 1;
         } else {
-            self.0 = 0;
+            self.value = 0;
         }
     }
 
-    /// Creates a value of type `PlayerId`. Returns None if value is >= N,
-    /// otherwise returns Some(PlayerId(value)).
+    /// Creates a value of type `PlayerId`, for the default number of players
+    /// of the game. Returns None if value is >= N, otherwise returns
+    /// Some(PlayerId(value)).
     ///
     /// # Examples
     ///
     /// ```
     /// use shuftlib::common::hands::PlayerId;
+    /// use shuftlib::tressette::TressetteRules;
     ///
-    /// let id = PlayerId::<4>::new(0);
+    /// let id = PlayerId::<TressetteRules>::new(0);
     /// assert!(id.is_some());
     ///
-    /// let id = PlayerId::<4>::new(4);
+    /// let id = PlayerId::<TressetteRules>::new(4);
     /// assert!(id.is_none());
     /// ```
     pub fn new(value: usize) -> Option<Self> {
-        if value < PLAYERS {
-            Some(PlayerId(value))
+        Self::with_players(value, G::PLAYERS)
+    }
+
+    /// Creates a `PlayerId` for a game played by the given number of players.
+    /// Returns None if value is >= `players`, or if the game can't be played
+    /// by that many players.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use shuftlib::common::hands::PlayerId;
+    /// use shuftlib::tressette::TressetteRules;
+    ///
+    /// // Tressette can also be played by 2 or 3 players.
+    /// let mut id = PlayerId::<TressetteRules>::with_players(2, 3).unwrap();
+    /// id.inc();
+    /// assert_eq!(*id, 0);
+    /// assert_eq!(id.players(), 3);
+    ///
+    /// assert!(PlayerId::<TressetteRules>::with_players(3, 3).is_none());
+    /// assert!(PlayerId::<TressetteRules>::with_players(0, 5).is_none());
+    /// ```
+    pub fn with_players(value: usize, players: usize) -> Option<Self> {
+        if value < players && players <= MAX_PLAYERS && G::supports(players) {
+            Some(PlayerId {
+                value,
+                players,
+                game: PhantomData,
+            })
         } else {
             None
         }
     }
+
+    /// Returns the id of another player of the same game.
+    pub fn other(&self, value: usize) -> Option<Self> {
+        Self::with_players(value, self.players)
+    }
 }
 
-impl<const PLAYERS: usize> TryFrom<usize> for PlayerId<PLAYERS> {
-    type Error = anyhow::Error;
+impl<G> PlayerId<G> {
+    /// The number of players of the game this id belongs to.
+    pub fn players(&self) -> usize {
+        self.players
+    }
+}
+
+impl<G: TrickTakingGame> TryFrom<usize> for PlayerId<G> {
+    type Error = ShuftError;
 
     fn try_from(value: usize) -> Result<Self, Self::Error> {
-        if (0..PLAYERS).contains(&value) {
-            Ok(PlayerId(value))
-        } else {
-            bail!(
-                "Tried to convert {} into a PlayerId, but acceptable values are in range 0..PLAYERS",
-                value
-            )
+        PlayerId::new(value).ok_or(ShuftError::InvalidPlayerId {
+            id: value,
+            players: G::PLAYERS,
+        })
+    }
+}
+
+// The traits below are implemented by hand, because deriving them would
+// require `G` to implement them too.
+
+impl<G: TrickTakingGame> Default for PlayerId<G> {
+    fn default() -> Self {
+        PlayerId {
+            value: 0,
+            players: G::PLAYERS,
+            game: PhantomData,
         }
     }
 }
 
-impl<const PLAYERS: usize> Display for PlayerId<PLAYERS> {
+impl<G> Clone for PlayerId<G> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<G> Copy for PlayerId<G> {}
+
+impl<G> PartialEq for PlayerId<G> {
+    fn eq(&self, other: &Self) -> bool {
+        (self.value, self.players) == (other.value, other.players)
+    }
+}
+
+impl<G> Eq for PlayerId<G> {}
+
+impl<G> PartialOrd for PlayerId<G> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<G> Ord for PlayerId<G> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        (self.value, self.players).cmp(&(other.value, other.players))
+    }
+}
+
+impl<G> Hash for PlayerId<G> {
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        self.value.hash(state);
+        self.players.hash(state);
+    }
+}
+
+impl<G> std::fmt::Debug for PlayerId<G> {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(f, "{}", self.0)
+        f.debug_tuple("PlayerId").field(&self.value).finish()
     }
 }
 
-impl<const PLAYERS: usize> Deref for PlayerId<PLAYERS> {
+impl<G> Display for PlayerId<G> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.value)
+    }
+}
+
+impl<G> Deref for PlayerId<G> {
     type Target = usize;
 
     fn deref(&self) -> &Self::Target {
-        &self.0
+        &self.value
     }
 }
 
 /// A trick is a set containing the cards played and the player who won the
 /// trick, represented as `PlayerId`.
-#[derive(Debug, Copy, Clone)]
+#[derive(Debug)]
 pub struct Trick<G>
 where
     G: TrickTakingGame,
-    [(); G::PLAYERS]:,
 {
-    cards: [G::CardType; G::PLAYERS],
-    taker: PlayerId<{ G::PLAYERS }>,
+    /// The cards played, indexed by `PlayerId`. Only the first
+    /// `taker.players()` are meaningful.
+    cards: [G::CardType; MAX_PLAYERS],
+    taker: PlayerId<G>,
+}
+
+impl<G: TrickTakingGame> Clone for Trick<G> {
+    fn clone(&self) -> Self {
+        *self
+    }
 }
 
+impl<G: TrickTakingGame> Copy for Trick<G> {}
+
 impl<G> Display for Trick<G>
 where
     G: TrickTakingGame,
-    [(); G::PLAYERS]:,
 {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(
-            f,
-            "{} {} {} {} {}",
-            self.cards[0], self.cards[1], self.cards[2], self.cards[3], self.taker
-        )
+        for card in self.cards() {
+            write!(f, "{} ", card)?;
+        }
+        write!(f, "{}", self.taker)
     }
 }
 
 impl<G> Trick<G>
 where
     G: TrickTakingGame,
-    [(); G::PLAYERS]:,
 {
     /// Returns the card this trick has been won with.
     pub fn taken_with(&self) -> G::CardType {
-        self.cards[self.taker.0]
+        self.cards[self.taker.value]
     }
 
     /// Getter for the `PlayerId` of the player who won the trick.
-    pub fn taker(&self) -> PlayerId<{ G::PLAYERS }> {
+    pub fn taker(&self) -> PlayerId<G> {
         self.taker
     }
 
-    /// Getter for the cards played during this trick.
+    /// Getter for the cards played during this trick, indexed by `PlayerId`.
     pub fn cards(&self) -> &[G::CardType] {
-        &self.cards
+        &self.cards[..self.players()]
+    }
+
+    /// The number of players of this trick.
+    pub fn players(&self) -> usize {
+        self.taker.players
     }
 }
 
 /// A temporary state of a trick that's still not over: not all the players made
 /// their move or a taker hasn't been determined yet.
-#[derive(Clone, Copy, Debug)]
+#[derive(Debug)]
 pub struct OngoingTrick<G>
 where
     G: TrickTakingGame,
-    [(); G::PLAYERS]:,
 {
-    cards: [Option<G::CardType>; G::PLAYERS],
-    first_to_play: PlayerId<{ G::PLAYERS }>,
-    next_to_play: PlayerId<{ G::PLAYERS }>,
+    cards: [Option<G::CardType>; MAX_PLAYERS],
+    first_to_play: PlayerId<G>,
+    next_to_play: PlayerId<G>,
     play_count: usize,
+    context: HandContext<G>,
 }
 
+impl<G: TrickTakingGame> Clone for OngoingTrick<G> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<G: TrickTakingGame> Copy for OngoingTrick<G> {}
+
 impl<G> Deref for OngoingTrick<G>
 where
     G: TrickTakingGame,
-    [(); G::PLAYERS]:,
 {
-    type Target = [Option<G::CardType>; G::PLAYERS];
+    type Target = [Option<G::CardType>];
 
     fn deref(&self) -> &Self::Target {
-        &self.cards
+        self.cards()
     }
 }
 
 impl<G> OngoingTrick<G>
 where
     G: TrickTakingGame,
-    [(); G::PLAYERS]:,
 {
-    /// Adds the `Card` passed as parameter to the `OngoingTrick`.
-    /// Checking the validity of the card played is a responsability of the
-    /// caller.
+    /// Plays a card from the hand of `player`, after checking that it's their
+    /// turn, that they hold the card and that the card is legal by the rules
+    /// of the game (see `TrickTakingGame::legal_moves`). The card is removed
+    /// from the hand of the player.
+    ///
+    /// # Errors
+    ///
+    /// Fails if the trick is already complete, if it's not the turn of
+    /// `player`, if they don't hold `card` or if the card can't be played. In
+    /// all these cases, neither the trick nor the player are modified.
+    ///
+    /// # Examples
+    /// ```
+    /// use shuftlib::common::{hands::{OngoingTrick, PlayError, Player, PlayerId}, cards::{ItalianRank, Suit}};
+    /// use shuftlib::error::ShuftError;
+    /// use shuftlib::tressette::{TressetteRules, TressetteCard};
+    ///
+    /// let mut first = Player::<TressetteRules>::new(PlayerId::new(0).unwrap());
+    /// let mut second = Player::new(PlayerId::new(1).unwrap());
+    /// first.give(TressetteCard::new(ItalianRank::Ace, Suit::Hearts));
+    /// second.give(TressetteCard::new(ItalianRank::Two, Suit::Hearts));
+    /// second.give(TressetteCard::new(ItalianRank::Three, Suit::Clubs));
+    ///
+    /// let mut trick = OngoingTrick::new(first.id());
+    /// let revoke = trick.play(&mut second, TressetteCard::new(ItalianRank::Three, Suit::Clubs));
+    /// assert_eq!(revoke, Err(ShuftError::IllegalPlay(PlayError::WrongTurn { expected: 0, found: 1 })));
+    ///
+    /// trick.play(&mut first, TressetteCard::new(ItalianRank::Ace, Suit::Hearts)).unwrap();
+    /// let revoke = trick.play(&mut second, TressetteCard::new(ItalianRank::Three, Suit::Clubs));
+    /// assert_eq!(revoke, Err(ShuftError::IllegalPlay(PlayError::Revoke)));
+    /// assert!(trick.play(&mut second, TressetteCard::new(ItalianRank::Two, Suit::Hearts)).is_ok());
+    /// assert_eq!(second.hand().len(), 1);
+    /// ```
+    pub fn play(&mut self, player: &mut Player<G>, card: G::CardType) -> Result<(), ShuftError> {
+        if self.is_complete() {
+            return Err(PlayError::TrickComplete.into());
+        }
+        if player.id() != self.next_to_play {
+            return Err(PlayError::WrongTurn {
+                expected: *self.next_to_play,
+                found: *player.id(),
+            }
+            .into());
+        }
+        if !player.hand().contains(card) {
+            return Err(PlayError::NotInHand.into());
+        }
+        if !G::is_legal(player.hand(), self, card) {
+            return Err(PlayError::Revoke.into());
+        }
+
+        player.remove(card);
+        self.play_unchecked(card);
+        Ok(())
+    }
+
+    /// Adds the `Card` passed as parameter to the `OngoingTrick`, for the
+    /// player whose turn it is, without any check. It's meant to replay
+    /// tricks coming from a trusted source: checking the validity of the card
+    /// played is a responsability of the caller.
     ///
     /// # Examples
     /// ```
-    /// #![feature(generic_const_exprs)]
     /// use shuftlib::common::{hands::{OngoingTrick, PlayerId, TrickTakingGame}, cards::{Card, ItalianRank, Suit}};
     /// use shuftlib::tressette::{TressetteRules, TressetteCard};
     ///
-    /// let first_to_play = PlayerId::<{TressetteRules::PLAYERS}>::new(0).unwrap();
+    /// let first_to_play = PlayerId::<TressetteRules>::new(0).unwrap();
     /// let card = TressetteCard::new(ItalianRank::Ace, Suit::Hearts);
-    /// let mut trick = OngoingTrick::<TressetteRules>::new(first_to_play);
-    /// trick.play(card);
+    /// let mut trick = OngoingTrick::new(first_to_play);
+    /// trick.play_unchecked(card);
     /// let mut second_to_play = first_to_play;
     /// second_to_play.inc();
     ///
     /// assert_eq!(trick[0], Some(card));
     /// assert_eq!(trick.next_to_play(), second_to_play)
     /// ```
-    pub fn play(&mut self, card: G::CardType) {
-        self.cards[self.next_to_play.0] = Some(card);
+    pub fn play_unchecked(&mut self, card: G::CardType) {
+        self.cards[self.next_to_play.value] = Some(card);
         self.next_to_play.inc();
         self.play_count += 1;
     }
 
+    /// The first card played in this trick, if any.
+    pub fn led(&self) -> Option<G::CardType> {
+        self.cards[self.first_to_play.value]
+    }
+
+    /// Whether every player already played a card in this trick.
+    pub fn is_complete(&self) -> bool {
+        self.play_count >= self.players()
+    }
+
     /// Tries to transform the current `OngoingTrick` into a `Trick` by
     /// determining the taker of the trick. It doesn't make any assumption on
     /// previously played cards during the current `OngoingHand`. It also does
@@ -319,15 +909,15 @@ where
     ///
     /// # Errors
     ///
-    /// Fails if any of the moves of the `OngoingTrick` this is called on is
-    /// None. It means that not all players made their move yet, so a taker
-    /// can't be determined.
+    /// Fails with `ShuftError::IncompleteTrick` if any of the moves of the
+    /// `OngoingTrick` this is called on is None. It means that not all players
+    /// made their move yet, so a taker can't be determined.
     ///
     /// # Examples
     ///
     /// ```
-    /// #![feature(generic_const_exprs)]
     /// use shuftlib::common::{hands::{OngoingTrick, PlayerId, TrickTakingGame}, cards::{ItalianRank, Suit}};
+    /// use shuftlib::error::ShuftError;
     /// use shuftlib::tressette::{TressetteRules, TressetteCard};
     ///
     /// let cards = [
@@ -336,17 +926,17 @@ where
     ///   TressetteCard::new(ItalianRank::Three, Suit::Hearts),
     ///   TressetteCard::new(ItalianRank::Four, Suit::Hearts),
     /// ];
-    /// let first_to_play = PlayerId::<{TressetteRules::PLAYERS}>::new(0).unwrap();
-    /// let mut ongoing_trick = OngoingTrick::<TressetteRules>::new(first_to_play);
-    /// ongoing_trick.play(cards[0]);
+    /// let first_to_play = PlayerId::<TressetteRules>::new(0).unwrap();
+    /// let mut ongoing_trick = OngoingTrick::new(first_to_play);
+    /// ongoing_trick.play_unchecked(cards[0]);
     ///
     /// // After only playing a card, it's not possible to finish the OngoingTrick.
-    /// assert!(ongoing_trick.clone().finish().is_none());
+    /// assert_eq!(ongoing_trick.clone().finish().unwrap_err(), ShuftError::IncompleteTrick);
     ///
     /// let mut to_play = first_to_play;
     /// to_play.inc();
     /// cards.iter().skip(1).for_each(|&c| {
-    ///   ongoing_trick.play(c);
+    ///   ongoing_trick.play_unchecked(c);
     ///   to_play.inc();
     /// });
     ///
@@ -354,47 +944,43 @@ where
     /// let trick = ongoing_trick.finish().unwrap();
     /// // Finishing the trick also means determining a taker. Since in this
     /// // example we are using the tressette game rules, player 2 is the taker.
-    /// assert_eq!(Some(trick.taker()), PlayerId::<{TressetteRules::PLAYERS}>::new(2));
+    /// assert_eq!(Some(trick.taker()), PlayerId::new(2));
     /// ```
-    pub fn finish(self) -> Option<Trick<G>> {
-        let mut cards: [G::CardType; G::PLAYERS] = [G::CardType::default(); G::PLAYERS];
-        if self
-            .iter()
-            .enumerate()
-            .map(|(i, &x)| {
-                if let Some(c) = x {
-                    cards[i] = c;
-                    true
-                } else {
-                    false
-                }
-            })
-            .any(|is_some| !is_some)
-        {
-            return None;
+    pub fn finish(self) -> Result<Trick<G>, ShuftError> {
+        let mut cards = [G::CardType::default(); MAX_PLAYERS];
+        for (card, &played) in cards.iter_mut().zip(self.cards()) {
+            *card = played.ok_or(ShuftError::IncompleteTrick)?;
         }
 
-        let taker = G::determine_taker(&cards, self.first_to_play);
-        Some(Trick { cards, taker })
+        let taker = G::determine_taker(&cards[..self.players()], self.first_to_play, &self.context);
+        Ok(Trick { cards, taker })
     }
 
-    /// Getter for the cards contained in this `OngoingTrick`.
+    /// Getter for the cards contained in this `OngoingTrick`, indexed by
+    /// `PlayerId`.
     pub fn cards(&self) -> &[Option<G::CardType>] {
-        &self.cards
+        &self.cards[..self.players()]
+    }
+
+    /// The number of players of this trick, which is the one of the
+    /// `PlayerId` it has been created with.
+    pub fn players(&self) -> usize {
+        self.first_to_play.players
     }
 
     /// Getter for the id of the person who starts the trick.
-    pub fn first_to_play(&self) -> PlayerId<{ G::PLAYERS }> {
+    pub fn first_to_play(&self) -> PlayerId<G> {
         self.first_to_play
     }
 
     /// Getter for the id of the person who playes last in the trick.
-    pub fn next_to_play(&self) -> PlayerId<{ G::PLAYERS }> {
+    pub fn next_to_play(&self) -> PlayerId<G> {
         self.next_to_play
     }
 
     /// Creates a new `OngoingTrick`, by defining the logic to determine the
-    /// taker.
+    /// taker. The trick is played by the number of players of
+    /// `first_to_play`.
     ///
     /// # Examples.
     ///
@@ -402,157 +988,820 @@ where
     /// use shuftlib::common::hands::{OngoingTrick, PlayerId, TrickTakingGame};
     /// use shuftlib::tressette::TressetteRules;
     ///
-    /// let first_to_play = PlayerId::<{TressetteRules::PLAYERS}>::new(0).unwrap();
-    /// let ongoing_trick = OngoingTrick::<TressetteRules>::new(first_to_play);
+    /// let first_to_play = PlayerId::<TressetteRules>::new(0).unwrap();
+    /// let ongoing_trick = OngoingTrick::new(first_to_play);
     ///
     /// assert_eq!(ongoing_trick.first_to_play(), first_to_play);
     /// ongoing_trick.cards().iter().for_each(|&c| assert!(c.is_none()));
     /// ```
-    pub fn new(first_to_play: PlayerId<{ G::PLAYERS }>) -> Self {
-        let mut last_to_play = first_to_play;
-        (0..G::PLAYERS - 1).for_each(|_| last_to_play.inc());
+    pub fn new(first_to_play: PlayerId<G>) -> Self {
+        Self::with_context(first_to_play, HandContext::new())
+    }
+
+    /// Creates a new `OngoingTrick`, played in a hand with the given trumps
+    /// and contract.
+    pub fn with_context(first_to_play: PlayerId<G>, context: HandContext<G>) -> Self {
         Self {
-            cards: [None; G::PLAYERS],
+            cards: [None; MAX_PLAYERS],
             first_to_play,
             next_to_play: first_to_play,
             play_count: 0,
+            context,
         }
     }
+
+    /// The trumps and the contract of the hand this trick is played in.
+    pub fn context(&self) -> &HandContext<G> {
+        &self.context
+    }
 }
 
 /// Various games are usually played multiple times, until one team reaches a
 /// certain score. These "multiple times" are called hands: "We played a game of
 /// tressette and our team won in just 2 hands!".
 ///
-/// This type is generic over the
-/// actual card type, the number of players allowed and the number of tricks it
-/// takes to finish the hand.
-#[derive(Debug, Clone, Copy)]
+/// This type is generic over the game, which defines the card type, the
+/// number of players and the number of tricks it takes to finish the hand.
+#[derive(Debug)]
 pub struct Hand<G>
 where
     G: TrickTakingGame,
-    [(); G::PLAYERS]:,
-    [(); G::TRICKS]:,
 {
-    tricks: [Trick<G>; G::TRICKS],
+    tricks: Vec<Trick<G>>,
+    context: HandContext<G>,
+    declarations: Vec<(PlayerId<G>, CardSet<G::CardType>)>,
+}
+
+impl<G: TrickTakingGame> Clone for Hand<G> {
+    fn clone(&self) -> Self {
+        Self {
+            tricks: self.tricks.clone(),
+            context: self.context,
+            declarations: self.declarations.clone(),
+        }
+    }
 }
 
 impl<G> Hand<G>
 where
     G: TrickTakingGame,
-    [(); G::PLAYERS]:,
-    [(); G::TRICKS]:,
 {
     /// Returns a reference to the tricks of this [`Hand<G>`].
-    pub fn tricks(&self) -> &[Trick<G>; G::TRICKS] {
+    pub fn tricks(&self) -> &[Trick<G>] {
         &self.tricks
     }
+
+    /// The trumps and the contract the hand has been played with, for
+    /// example to score it against the contract.
+    pub fn context(&self) -> &HandContext<G> {
+        &self.context
+    }
+
+    /// The combinations declared by the players, with the cards they showed,
+    /// in the order they have been declared.
+    pub fn declarations(&self) -> &[(PlayerId<G>, CardSet<G::CardType>)] {
+        &self.declarations
+    }
+
+    /// The number of players of this hand.
+    pub fn players(&self) -> usize {
+        self.tricks.first().map_or(G::PLAYERS, Trick::players)
+    }
 }
 
 /// A hand takes multiple turns for each player to be completed, this is the
-/// representation of a `Hand` which hasn't been completed yet.
-#[derive(Clone, Copy, Debug)]
+/// representation of a `Hand` which hasn't been completed yet. It owns the
+/// trick being played: cards are played into it and, when every player
+/// played, the trick is stored and the next one is started, led by the taker
+/// of the previous one.
+#[derive(Debug)]
 pub struct OngoingHand<G>
 where
     G: TrickTakingGame,
-    [(); G::PLAYERS]:,
-    [(); G::TRICKS]:,
 {
+    first_to_play: PlayerId<G>,
+    context: HandContext<G>,
     current_trick: Option<OngoingTrick<G>>,
     index: usize,
-    tricks: [Option<Trick<G>>; G::TRICKS],
+    /// Contains one slot for each trick of the hand.
+    tricks: Vec<Option<Trick<G>>>,
+    declarations: Vec<(PlayerId<G>, CardSet<G::CardType>)>,
+}
+
+impl<G: TrickTakingGame> Clone for OngoingHand<G> {
+    fn clone(&self) -> Self {
+        Self {
+            first_to_play: self.first_to_play,
+            context: self.context,
+            current_trick: self.current_trick,
+            index: self.index,
+            tricks: self.tricks.clone(),
+            declarations: self.declarations.clone(),
+        }
+    }
 }
 
 impl<G> OngoingHand<G>
 where
     G: TrickTakingGame,
-    [(); G::PLAYERS]:,
-    [(); G::TRICKS]:,
 {
-    /// Returns the current trick of this [`OngoingHand<G>`].
-    pub fn current_trick(&self) -> &Option<OngoingTrick<G>> {
-        &self.current_trick
+    /// Returns the trick being played, or None if the hand is complete.
+    pub fn current_trick(&self) -> Option<&OngoingTrick<G>> {
+        self.current_trick.as_ref()
     }
 
     /// Returns a reference to the tricks of this [`OngoingHand<G>`].
-    pub fn tricks(&self) -> &[Option<Trick<G>>; G::TRICKS] {
+    pub fn tricks(&self) -> &[Option<Trick<G>>] {
         &self.tricks
     }
 
-    /// Returns the index of this [`OngoingHand<G>`].
+    /// Returns the index of the trick being played, which is also the
+    /// number of tricks completed so far.
     pub fn index(&self) -> usize {
         self.index
     }
 
-    /// Transforms an `OngoingHand` into a `Hand`, a read-only data structure
-    /// used to just story the information related to a hand that has been played.
-    pub fn finish(self) -> Option<Hand<G>> {
-        if self.tricks.iter().any(|t| t.is_none()) {
-            return None;
+    /// The player who leads the first trick of the hand.
+    pub fn first_to_play(&self) -> PlayerId<G> {
+        self.first_to_play
+    }
+
+    /// The trumps and the contract of this hand.
+    pub fn context(&self) -> &HandContext<G> {
+        &self.context
+    }
+
+    /// The combinations declared so far, with the cards shown by the players,
+    /// in the order they have been declared.
+    pub fn declarations(&self) -> &[(PlayerId<G>, CardSet<G::CardType>)] {
+        &self.declarations
+    }
+
+    /// Sets the trumps and the contract of this hand, which are used by
+    /// every trick from the current one on.
+    pub fn with_context(mut self, context: HandContext<G>) -> Self {
+        self.context = context;
+        if let Some(trick) = &mut self.current_trick {
+            trick.context = context;
+        }
+        self
+    }
+
+    /// The player who leads the trick at `index`: the first to play for the
+    /// first trick, the taker of the previous trick for the other ones. It's
+    /// None if the previous trick hasn't been completed yet.
+    pub fn leader(&self, index: usize) -> Option<PlayerId<G>> {
+        match index.checked_sub(1) {
+            None => Some(self.first_to_play),
+            Some(previous) => self.tricks.get(previous)?.as_ref().map(Trick::taker),
         }
+    }
 
-        let tricks: [Trick<G>; G::TRICKS] = self
-            .tricks
-            .into_iter()
-            .flatten()
-            .collect::<Vec<_>>()
-            .try_into()
-            .ok()?;
-        Some(Hand { tricks })
+    /// Whether every trick of the hand has been played.
+    pub fn is_complete(&self) -> bool {
+        self.index >= self.tricks.len()
+    }
+
+    /// Plays `card` for `player` in the current trick, checking that the move
+    /// is legal. When every player played, the trick is stored and the next
+    /// one is started, with its taker as first to play.
+    ///
+    /// # Errors
+    ///
+    /// Fails with `ShuftError::HandComplete` if every trick has already been
+    /// played, or if the card can't be played, see `OngoingTrick::play`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use shuftlib::common::{cards::{ItalianRank, Suit}, hands::{OngoingHand, Player, PlayerId}};
+    /// use shuftlib::tressette::{TressetteCard, TressetteRules};
+    ///
+    /// let mut players: Vec<Player<TressetteRules>> =
+    ///     (0..4).map(|i| Player::new(PlayerId::new(i).unwrap())).collect();
+    /// let ranks = [ItalianRank::Four, ItalianRank::Three, ItalianRank::Five, ItalianRank::Six];
+    /// for (player, rank) in players.iter_mut().zip(ranks) {
+    ///     player.give(TressetteCard::new(rank, Suit::Clubs));
+    /// }
+    ///
+    /// let mut ongoing_hand = OngoingHand::with_tricks(PlayerId::new(0).unwrap(), 1);
+    /// for (player, rank) in players.iter_mut().zip(ranks) {
+    ///     ongoing_hand.play(player, TressetteCard::new(rank, Suit::Clubs)).unwrap();
+    /// }
+    ///
+    /// // The three of clubs takes the trick.
+    /// assert!(ongoing_hand.is_complete());
+    /// assert_eq!(ongoing_hand.tricks()[0].unwrap().taker(), PlayerId::new(1).unwrap());
+    /// ```
+    pub fn play(&mut self, player: &mut Player<G>, card: G::CardType) -> Result<(), ShuftError> {
+        let trick = self
+            .current_trick
+            .as_mut()
+            .ok_or(ShuftError::HandComplete)?;
+        trick.play(player, card)?;
+        if trick.is_complete() {
+            let trick = (*trick).finish()?;
+            self.push(trick);
+        }
+
+        Ok(())
+    }
+
+    /// Transforms an `OngoingHand` into a `Hand`, a read-only data structure
+    /// used to just story the information related to a hand that has been played.
+    ///
+    /// # Errors
+    ///
+    /// Fails if not every trick of the hand has been played.
+    pub fn finish(self) -> Result<Hand<G>, ShuftError> {
+        let tricks = self.tricks.into_iter().collect::<Option<Vec<_>>>();
+        let tricks = tricks.ok_or(ShuftError::IncompleteHand)?;
+        Ok(Hand {
+            tricks,
+            context: self.context,
+            declarations: self.declarations,
+        })
     }
 
-    /// Constructor for `OngoingHand`. All the internal fields are initialized
-    /// as empty or None.
+    /// Constructor for `OngoingHand`, where `first_to_play` leads the first
+    /// trick. No trick has been played yet.
     ///
     /// # Examples
     ///
     /// ```
-    /// use shuftlib::{common::{hands::OngoingHand}, tressette::TressetteRules};
+    /// use shuftlib::{common::hands::{OngoingHand, PlayerId}, tressette::TressetteRules};
     ///
-    /// let ongoing_hand = OngoingHand::<TressetteRules>::new();
+    /// let first_to_play = PlayerId::new(1).unwrap();
+    /// let ongoing_hand = OngoingHand::<TressetteRules>::new(first_to_play);
     ///
     /// assert_eq!(ongoing_hand.index(), 0);
-    /// assert!(ongoing_hand.current_trick().is_none());
+    /// assert_eq!(ongoing_hand.current_trick().unwrap().first_to_play(), first_to_play);
     /// ongoing_hand.tricks().iter().for_each(|t| assert!(t.is_none()));
     /// ```
-    pub fn new() -> Self {
-        let tricks: [Option<Trick<G>>; G::TRICKS] = array_init::array_init(|_| None);
+    pub fn new(first_to_play: PlayerId<G>) -> Self {
+        Self::with_tricks(first_to_play, G::TRICKS)
+    }
 
-        let current_trick = None;
-        let index = 0;
+    /// Creates an `OngoingHand` made of the given number of tricks, for games
+    /// where it depends on the number of players.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use shuftlib::{common::hands::{OngoingHand, PlayerId}, tressette::TressetteRules};
+    ///
+    /// // With 3 players, each one of them receives 13 cards.
+    /// let first_to_play = PlayerId::with_players(0, 3).unwrap();
+    /// let ongoing_hand = OngoingHand::<TressetteRules>::with_tricks(first_to_play, 13);
+    /// assert_eq!(ongoing_hand.tricks().len(), 13);
+    /// ```
+    pub fn with_tricks(first_to_play: PlayerId<G>, tricks: usize) -> Self {
+        let tricks: Vec<_> = (0..tricks).map(|_| None).collect();
+        let current_trick = (!tricks.is_empty()).then(|| OngoingTrick::new(first_to_play));
 
         Self {
-            tricks,
+            first_to_play,
+            context: HandContext::new(),
             current_trick,
-            index,
+            index: 0,
+            tricks,
+            declarations: Vec::new(),
         }
     }
 
-    /// Adds a trick to this hand.
-    pub fn add(&mut self, trick: Trick<G>, id: usize) {
-        self.tricks[id] = Some(trick);
+    /// Adds a trick that has been played elsewhere to this hand, as the trick
+    /// at index `id`. Tricks must be added in order, and only while no card
+    /// has been played in the current trick.
+    ///
+    /// # Errors
+    ///
+    /// Fails with `ShuftError::HandComplete` if every trick has already been
+    /// played, with `ShuftError::UnexpectedTrick` if `id` isn't the index of
+    /// the current trick or if cards have already been played in it, and with
+    /// `ShuftError::InvalidPlayers` if the trick has been played by a
+    /// different number of players.
+    pub fn add(&mut self, trick: Trick<G>, id: usize) -> Result<(), ShuftError> {
+        let current = self
+            .current_trick
+            .as_ref()
+            .ok_or(ShuftError::HandComplete)?;
+        if id != self.index || current.cards().iter().any(Option::is_some) {
+            return Err(ShuftError::UnexpectedTrick {
+                id,
+                expected: self.index,
+            });
+        }
+        if trick.players() != current.players() {
+            return Err(ShuftError::InvalidPlayers {
+                found: trick.players(),
+                expected: current.players(),
+            });
+        }
+
+        self.push(trick);
+        Ok(())
+    }
+
+    /// Stores a completed trick at the current index and starts the next one,
+    /// led by its taker.
+    fn push(&mut self, trick: Trick<G>) {
+        self.tricks[self.index] = Some(trick);
+        self.index += 1;
+        self.current_trick = (self.index < self.tricks.len())
+            .then(|| OngoingTrick::with_context(trick.taker(), self.context));
+    }
+}
+
+impl<G> OngoingHand<G>
+where
+    G: Declarations,
+{
+    /// Declares the combination made of `cards`, held by `player`, who must
+    /// be at their first turn: it's their turn to play in the first trick of
+    /// the hand. A player can declare several combinations, but each one
+    /// only once.
+    ///
+    /// # Errors
+    ///
+    /// Fails with `ShuftError::HandComplete` if every trick has already been
+    /// played, or with `ShuftError::IllegalDeclaration` if the player isn't
+    /// at their first turn or the combination can't be declared, see
+    /// `Declarations::can_declare`.
+    pub fn declare(
+        &mut self,
+        player: &Player<G>,
+        cards: CardSet<G::CardType>,
+    ) -> Result<(), ShuftError> {
+        let trick = self
+            .current_trick
+            .as_ref()
+            .ok_or(ShuftError::HandComplete)?;
+        if self.index > 0 || trick.next_to_play() != player.id() {
+            return Err(DeclarationError::NotFirstTurn.into());
+        }
+        if !G::can_declare(player.hand(), &cards) {
+            return Err(DeclarationError::NotHeld.into());
+        }
+        if self.declarations.contains(&(player.id(), cards)) {
+            return Err(DeclarationError::AlreadyDeclared.into());
+        }
+
+        self.declarations.push((player.id(), cards));
+        Ok(())
     }
 }
 
 impl<G> Default for OngoingHand<G>
 where
     G: TrickTakingGame,
-    [(); G::PLAYERS]:,
-    [(); G::TRICKS]:,
 {
     fn default() -> Self {
-        Self::new()
+        Self::new(PlayerId::default())
+    }
+}
+
+#[cfg(feature = "serde")]
+mod serde_impls {
+    use serde::{
+        de::Error as _, ser::SerializeStruct, Deserialize, Deserializer, Serialize, Serializer,
+    };
+
+    use super::{
+        Hand, HandContext, OngoingHand, OngoingTrick, Player, PlayerId, Trick, TrickTakingGame,
+        MAX_PLAYERS,
+    };
+    use crate::common::cards::{CardSet, Suit};
+
+    /// Player ids are stored as numbers, without the number of players, which
+    /// is stored once by the types containing them.
+    impl<G> Serialize for PlayerId<G> {
+        fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+            serializer.serialize_u64(self.value as u64)
+        }
+    }
+
+    /// A single id is deserialized for the default number of players of the
+    /// game.
+    impl<'de, G: TrickTakingGame> Deserialize<'de> for PlayerId<G> {
+        fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+            let value = usize::deserialize(deserializer)?;
+            player_id(value, G::PLAYERS)
+        }
+    }
+
+    /// Creates the id of a player of a game played by `players` players.
+    fn player_id<G: TrickTakingGame, E: serde::de::Error>(
+        value: usize,
+        players: usize,
+    ) -> Result<PlayerId<G>, E> {
+        PlayerId::with_players(value, players).ok_or_else(|| {
+            E::custom(format!(
+                "invalid player id {} for a game with {} players",
+                value, players
+            ))
+        })
+    }
+
+    /// Copies `items` at the beginning of an array of `MAX_PLAYERS`
+    /// elements, filling the rest with `fill`. The length of `items` must
+    /// have already been checked.
+    fn to_array<T: Copy>(items: &[T], fill: T) -> [T; MAX_PLAYERS] {
+        let mut array = [fill; MAX_PLAYERS];
+        array[..items.len()].copy_from_slice(items);
+        array
+    }
+
+    /// Checks that all the tricks have been played by the same number of
+    /// players.
+    fn check_players<E: serde::de::Error>(
+        mut players: impl Iterator<Item = usize>,
+    ) -> Result<(), E> {
+        match players.next() {
+            Some(first) if players.any(|p| p != first) => Err(E::custom(
+                "all the tricks of a hand must have the same number of players",
+            )),
+            _ => Ok(()),
+        }
+    }
+
+    #[derive(Deserialize)]
+    #[serde(rename = "Player")]
+    struct PlayerRepr<H> {
+        hand: H,
+        id: usize,
+        players: Option<usize>,
+    }
+
+    impl<G> Serialize for Player<G>
+    where
+        G: TrickTakingGame,
+        G::CardType: Serialize,
+    {
+        fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+            let mut state = serializer.serialize_struct("Player", 3)?;
+            state.serialize_field("hand", &self.hand)?;
+            state.serialize_field("id", &self.id)?;
+            state.serialize_field("players", &self.id.players())?;
+            state.end()
+        }
+    }
+
+    impl<'de, G> Deserialize<'de> for Player<G>
+    where
+        G: TrickTakingGame,
+        G::CardType: Deserialize<'de>,
+    {
+        /// When `players` is missing, the default number of players of the
+        /// game is used.
+        fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+            let PlayerRepr { hand, id, players } = PlayerRepr::deserialize(deserializer)?;
+            let id = player_id(id, players.unwrap_or(G::PLAYERS))?;
+            Ok(Player { hand, id })
+        }
+    }
+
+    #[derive(Deserialize)]
+    #[serde(rename = "Trick")]
+    struct TrickRepr<C> {
+        cards: Vec<C>,
+        taker: usize,
+    }
+
+    impl<G> Serialize for Trick<G>
+    where
+        G: TrickTakingGame,
+        G::CardType: Serialize,
+    {
+        fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+            let mut state = serializer.serialize_struct("Trick", 2)?;
+            state.serialize_field("cards", self.cards())?;
+            state.serialize_field("taker", &self.taker)?;
+            state.end()
+        }
+    }
+
+    impl<'de, G> Deserialize<'de> for Trick<G>
+    where
+        G: TrickTakingGame,
+        G::CardType: Deserialize<'de>,
+    {
+        /// The number of players is given by the number of cards.
+        fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+            let TrickRepr { cards, taker } = TrickRepr::deserialize(deserializer)?;
+            let taker = player_id(taker, cards.len())?;
+            let cards = to_array(&cards, G::CardType::default());
+            Ok(Trick { cards, taker })
+        }
+    }
+
+    #[derive(Deserialize)]
+    #[serde(rename = "HandContext")]
+    struct HandContextRepr<S, K> {
+        trump: Option<Suit>,
+        permanent_trumps: S,
+        contract: Option<K>,
+        #[serde(default)]
+        stock: usize,
+    }
+
+    impl<G> Serialize for HandContext<G>
+    where
+        G: TrickTakingGame,
+        G::CardType: Serialize,
+        G::Contract: Serialize,
+    {
+        fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+            let mut state = serializer.serialize_struct("HandContext", 4)?;
+            state.serialize_field("trump", &self.trump)?;
+            state.serialize_field("permanent_trumps", &self.permanent_trumps)?;
+            state.serialize_field("contract", &self.contract)?;
+            state.serialize_field("stock", &self.stock)?;
+            state.end()
+        }
+    }
+
+    impl<'de, G> Deserialize<'de> for HandContext<G>
+    where
+        G: TrickTakingGame,
+        G::CardType: Deserialize<'de>,
+        G::Contract: Deserialize<'de>,
+    {
+        fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+            let HandContextRepr {
+                trump,
+                permanent_trumps,
+                contract,
+                stock,
+            } = HandContextRepr::<CardSet<G::CardType>, G::Contract>::deserialize(deserializer)?;
+            Ok(HandContext {
+                trump,
+                permanent_trumps,
+                contract,
+                stock,
+            })
+        }
+    }
+
+    #[derive(Deserialize)]
+    #[serde(rename = "OngoingTrick")]
+    struct OngoingTrickRepr<C, X> {
+        cards: Vec<Option<C>>,
+        first_to_play: usize,
+        context: Option<X>,
+    }
+
+    impl<G> Serialize for OngoingTrick<G>
+    where
+        G: TrickTakingGame,
+        G::CardType: Serialize,
+        G::Contract: Serialize,
+    {
+        /// The context is only stored when the hand has trumps, a contract or a
+        /// stock.
+        fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+            let mut state = serializer.serialize_struct("OngoingTrick", 3)?;
+            state.serialize_field("cards", self.cards())?;
+            state.serialize_field("first_to_play", &self.first_to_play)?;
+            if self.context == HandContext::new() {
+                state.skip_field("context")?;
+            } else {
+                state.serialize_field("context", &self.context)?;
+            }
+            state.end()
+        }
+    }
+
+    impl<'de, G> Deserialize<'de> for OngoingTrick<G>
+    where
+        G: TrickTakingGame,
+        G::CardType: Deserialize<'de>,
+        G::Contract: Deserialize<'de>,
+    {
+        /// Only the cards and the first player are stored: the number of
+        /// players is given by the number of slots, while the number of plays
+        /// and the next player are recomputed, after checking that the cards
+        /// have been played in turn starting from `first_to_play`.
+        fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+            let OngoingTrickRepr {
+                cards,
+                first_to_play,
+                context,
+            } = OngoingTrickRepr::<G::CardType, HandContext<G>>::deserialize(deserializer)?;
+            let first_to_play: PlayerId<G> = player_id(first_to_play, cards.len())?;
+
+            let play_count = cards.iter().filter(|c| c.is_some()).count();
+            let mut next_to_play = first_to_play;
+            for i in 0..cards.len() {
+                if (i < play_count) != cards[*next_to_play].is_some() {
+                    return Err(D::Error::custom(
+                        "the cards of the trick must be played in turn, starting from first_to_play",
+                    ));
+                }
+                if i < play_count {
+                    next_to_play.inc();
+                }
+            }
+
+            Ok(OngoingTrick {
+                cards: to_array(&cards, None),
+                first_to_play,
+                next_to_play,
+                play_count,
+                context: context.unwrap_or_default(),
+            })
+        }
+    }
+
+    impl<G> Serialize for Hand<G>
+    where
+        G: TrickTakingGame,
+        G::CardType: Serialize,
+        G::Contract: Serialize,
+    {
+        /// The context is only stored when the hand has trumps, a contract or a
+        /// stock, and the declarations only when there are any.
+        fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+            let mut state = serializer.serialize_struct("Hand", 3)?;
+            state.serialize_field("tricks", &self.tricks)?;
+            if self.context == HandContext::new() {
+                state.skip_field("context")?;
+            } else {
+                state.serialize_field("context", &self.context)?;
+            }
+            if self.declarations.is_empty() {
+                state.skip_field("declarations")?;
+            } else {
+                state.serialize_field("declarations", &self.declarations)?;
+            }
+            state.end()
+        }
+    }
+
+    #[derive(Deserialize)]
+    #[serde(rename = "Hand")]
+    struct HandRepr<T, X, D> {
+        tricks: Vec<T>,
+        context: Option<X>,
+        #[serde(default = "Vec::new")]
+        declarations: Vec<(usize, D)>,
+    }
+
+    impl<'de, G> Deserialize<'de> for Hand<G>
+    where
+        G: TrickTakingGame,
+        G::CardType: Deserialize<'de>,
+        G::Contract: Deserialize<'de>,
+    {
+        fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+            let HandRepr {
+                tricks,
+                context,
+                declarations,
+            } = HandRepr::<Trick<G>, HandContext<G>, CardSet<G::CardType>>::deserialize(
+                deserializer,
+            )?;
+            check_players(tricks.iter().map(Trick::players))?;
+            let players = tricks.first().map_or(G::PLAYERS, Trick::players);
+            Ok(Hand {
+                tricks,
+                context: context.unwrap_or_default(),
+                declarations: declarations_of(declarations, players)?,
+            })
+        }
+    }
+
+    #[derive(Deserialize)]
+    #[serde(rename = "OngoingHand")]
+    struct OngoingHandRepr<O, T, X, D> {
+        first_to_play: usize,
+        context: Option<X>,
+        current_trick: Option<O>,
+        index: usize,
+        tricks: Vec<Option<T>>,
+        #[serde(default = "Vec::new")]
+        declarations: Vec<(usize, D)>,
+    }
+
+    impl<G> Serialize for OngoingHand<G>
+    where
+        G: TrickTakingGame,
+        G::CardType: Serialize,
+        G::Contract: Serialize,
+    {
+        fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+            let mut state = serializer.serialize_struct("OngoingHand", 6)?;
+            state.serialize_field("first_to_play", &self.first_to_play)?;
+            if self.context == HandContext::new() {
+                state.skip_field("context")?;
+            } else {
+                state.serialize_field("context", &self.context)?;
+            }
+            state.serialize_field("current_trick", &self.current_trick)?;
+            state.serialize_field("index", &self.index)?;
+            state.serialize_field("tricks", &self.tricks)?;
+            if self.declarations.is_empty() {
+                state.skip_field("declarations")?;
+            } else {
+                state.serialize_field("declarations", &self.declarations)?;
+            }
+            state.end()
+        }
+    }
+
+    impl<'de, G> Deserialize<'de> for OngoingHand<G>
+    where
+        G: TrickTakingGame,
+        G::CardType: Deserialize<'de>,
+        G::Contract: Deserialize<'de>,
+    {
+        fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+            let OngoingHandRepr {
+                first_to_play,
+                context,
+                current_trick,
+                index,
+                tricks,
+                declarations,
+            } = OngoingHandRepr::<OngoingTrick<G>, Trick<G>, HandContext<G>, CardSet<G::CardType>>::deserialize(
+                deserializer,
+            )?;
+            if index > tricks.len() {
+                return Err(D::Error::custom(format!(
+                    "invalid trick index {}, expected a value in range 0..={}",
+                    index,
+                    tricks.len()
+                )));
+            }
+            if tricks
+                .iter()
+                .enumerate()
+                .any(|(i, t)| t.is_some() != (i < index))
+            {
+                return Err(D::Error::custom(
+                    "only the tricks before the current index must have been played",
+                ));
+            }
+            let players = tricks
+                .iter()
+                .flatten()
+                .map(Trick::players)
+                .chain(current_trick.iter().map(OngoingTrick::players));
+            let players = players.collect::<Vec<_>>();
+            check_players(players.iter().copied())?;
+            let players = players.first().copied().unwrap_or(G::PLAYERS);
+            let first_to_play = player_id(first_to_play, players)?;
+
+            // The current trick is played with the context of the hand.
+            let hand = OngoingHand {
+                first_to_play,
+                context: HandContext::new(),
+                current_trick,
+                index,
+                tricks,
+                declarations: declarations_of(declarations, players)?,
+            }
+            .with_context(context.unwrap_or_default());
+            let leader = hand.leader(index);
+            match hand.current_trick() {
+                Some(trick)
+                    if index < hand.tricks.len() && Some(trick.first_to_play()) == leader =>
+                {
+                    Ok(hand)
+                }
+                None if index == hand.tricks.len() => Ok(hand),
+                _ => Err(D::Error::custom(
+                    "the current trick must be led by the taker of the previous one",
+                )),
+            }
+        }
+    }
+
+    type Declarations<G> = Vec<(PlayerId<G>, CardSet<<G as TrickTakingGame>::CardType>)>;
+
+    /// Converts the declarations stored with the seat of their player into
+    /// declarations of a hand played by `players` players.
+    fn declarations_of<G: TrickTakingGame, E: serde::de::Error>(
+        declarations: Vec<(usize, CardSet<G::CardType>)>,
+        players: usize,
+    ) -> Result<Declarations<G>, E> {
+        declarations
+            .into_iter()
+            .map(|(seat, cards)| Ok((player_id(seat, players)?, cards)))
+            .collect()
     }
 }
 
 #[cfg(test)]
+#[allow(clippy::unwrap_used)]
 mod tests {
     use proptest::collection::hash_set;
     use proptest::{array, prelude::*};
 
+    use std::cmp::Ordering;
+
     use crate::common::cards::{ItalianCard, ItalianRank, Suit};
 
-    use super::{OngoingTrick, PlayerId, TrickTakingGame};
+    use super::{HandContext, OngoingTrick, PlayerId, TrickStrength, TrickTakingGame, MAX_PLAYERS};
 
     /// Strategy to create a random `TressetteCard`.
     fn italian_card_strategy() -> impl Strategy<Value = ItalianCard> {
@@ -584,15 +1833,18 @@ mod tests {
 
     impl TrickTakingGame for TestGame {
         type CardType = ItalianCard;
+        type Contract = ();
+        type Bid = ();
 
         const PLAYERS: usize = 4;
 
         const TRICKS: usize = 10;
 
         fn determine_taker(
-            _cards: &[Self::CardType; Self::PLAYERS],
-            _first_to_play: super::PlayerId<{ Self::PLAYERS }>,
-        ) -> super::PlayerId<{ Self::PLAYERS }> {
+            _cards: &[Self::CardType],
+            _first_to_play: PlayerId<Self>,
+            _context: &HandContext<Self>,
+        ) -> PlayerId<Self> {
             PlayerId::new(0).unwrap()
         }
     }
@@ -603,7 +1855,7 @@ mod tests {
     /// a specific value.
     fn ongoing_trick_strategy() -> impl Strategy<Value = OngoingTrick<TestGame>> {
         hash_set(italian_card_strategy(), TestGame::PLAYERS).prop_map(|hash_set| {
-            let mut cards = [None; TestGame::PLAYERS];
+            let mut cards = [None; MAX_PLAYERS];
             hash_set
                 .iter()
                 .enumerate()
@@ -611,9 +1863,10 @@ mod tests {
 
             OngoingTrick {
                 cards,
-                first_to_play: PlayerId(0),
-                next_to_play: PlayerId(0),
+                first_to_play: PlayerId::default(),
+                next_to_play: PlayerId::default(),
                 play_count: 0,
+                context: HandContext::new(),
             }
         })
     }
@@ -625,7 +1878,7 @@ mod tests {
 
             for (index, &card) in cards.iter().enumerate() {
                 // Panicking if there are duplicates in the cards array.
-                trick.play(card);
+                trick.play_unchecked(card);
                 // If the card was successfully played, it will be contained
                 // inside the `OngoingTrick` struct as `Some`.
                 assert_eq!(trick[index], Some(card));
@@ -642,4 +1895,331 @@ mod tests {
             prop_assert_eq!(trick.taken_with(), cards[0].unwrap());
         }
     }
+
+    #[test]
+    fn checked_play_rejects_illegal_moves() {
+        use super::{PlayError, Player};
+
+        let mut players: Vec<Player<TestGame>> = (0..TestGame::PLAYERS)
+            .map(|i| Player::new(PlayerId::new(i).unwrap()))
+            .collect();
+        // Every player holds the ace and the two of a different suit.
+        for (i, player) in players.iter_mut().enumerate() {
+            let suit = Suit::from_repr(i).unwrap();
+            player.give(ItalianCard::new(ItalianRank::Ace, suit));
+            player.give(ItalianCard::new(ItalianRank::Two, suit));
+        }
+
+        let mut trick = OngoingTrick::<TestGame>::new(PlayerId::new(3).unwrap());
+        let not_held = ItalianCard::new(ItalianRank::King, Suit::Spades);
+        assert_eq!(
+            trick.play(&mut players[3], not_held),
+            Err(PlayError::NotInHand.into())
+        );
+
+        let lead = ItalianCard::new(ItalianRank::Ace, Suit::Spades);
+        trick.play(&mut players[3], lead).unwrap();
+        assert_eq!(trick.led(), Some(lead));
+        assert!(!players[3].hand().contains(lead));
+
+        // Player 0 has no spades, so any card is legal.
+        trick
+            .play(
+                &mut players[0],
+                ItalianCard::new(ItalianRank::Two, Suit::Hearts),
+            )
+            .unwrap();
+        for player in &mut players[1..3] {
+            let card = player.hand().iter().next().unwrap();
+            trick.play(player, card).unwrap();
+        }
+
+        assert!(trick.is_complete());
+        let card = players[3].hand().iter().next().unwrap();
+        assert_eq!(
+            trick.play(&mut players[3], card),
+            Err(PlayError::TrickComplete.into())
+        );
+        assert_eq!(players[3].hand().len(), 1);
+    }
+
+    #[test]
+    fn ongoing_hand_tracks_its_progress() {
+        use super::{OngoingHand, Player};
+        use crate::error::ShuftError;
+
+        let first_to_play = PlayerId::<TestGame>::new(2).unwrap();
+        let mut hand = OngoingHand::with_tricks(first_to_play, 2);
+        let mut trick = OngoingTrick::new(first_to_play);
+        for rank in [
+            ItalianRank::Ace,
+            ItalianRank::Two,
+            ItalianRank::Three,
+            ItalianRank::Four,
+        ] {
+            trick.play_unchecked(ItalianCard::new(rank, Suit::Clubs));
+        }
+        let trick = trick.finish().unwrap();
+
+        assert_eq!(
+            hand.add(trick, 1),
+            Err(ShuftError::UnexpectedTrick { id: 1, expected: 0 })
+        );
+        hand.add(trick, 0).unwrap();
+        assert_eq!(
+            hand.add(trick, 0),
+            Err(ShuftError::UnexpectedTrick { id: 0, expected: 1 })
+        );
+        assert_eq!(hand.index(), 1);
+        assert_eq!(hand.leader(0), Some(first_to_play));
+        assert_eq!(hand.leader(1), Some(trick.taker()));
+        assert_eq!(hand.current_trick().unwrap().first_to_play(), trick.taker());
+
+        // The second trick is played card by card, starting from the taker.
+        let mut players: Vec<Player<TestGame>> = (0..TestGame::PLAYERS)
+            .map(|i| Player::new(PlayerId::new(i).unwrap()))
+            .collect();
+        for (i, player) in players.iter_mut().enumerate() {
+            player.give(ItalianCard::new(
+                ItalianRank::Ace,
+                Suit::from_repr(i).unwrap(),
+            ));
+        }
+        for player in &mut players {
+            let card = player.hand().iter().next().unwrap();
+            hand.play(player, card).unwrap();
+        }
+
+        assert!(hand.is_complete());
+        assert!(hand.current_trick().is_none());
+        assert_eq!(hand.add(trick, 2), Err(ShuftError::HandComplete));
+        assert_eq!(hand.finish().unwrap().tricks().len(), 2);
+    }
+
+    /// A game with a trump suit and permanent trumps, decided by a contract
+    /// which is just a number.
+    #[derive(Clone, Copy, Debug)]
+    struct TrumpGame {}
+
+    impl TrickTakingGame for TrumpGame {
+        type CardType = ItalianCard;
+        type Contract = u8;
+        type Bid = ();
+
+        const PLAYERS: usize = 4;
+
+        const TRICKS: usize = 10;
+
+        fn determine_taker(
+            cards: &[Self::CardType],
+            first_to_play: PlayerId<Self>,
+            context: &HandContext<Self>,
+        ) -> PlayerId<Self> {
+            super::highest_trump_or_led(cards, first_to_play, context)
+        }
+    }
+
+    impl TrickStrength for TrumpGame {
+        /// Cards following the led card are ranked from the ace to the king,
+        /// and the permanent trump of the trump suit beats the other ones, like
+        /// the right bower of euchre.
+        fn compare(
+            led: &ItalianCard,
+            a: &ItalianCard,
+            b: &ItalianCard,
+            context: &HandContext<Self>,
+        ) -> Ordering {
+            let strength = |c: &ItalianCard| {
+                let permanent = context.permanent_trumps().contains(*c);
+                (permanent || c.suit() == led.suit()).then_some((
+                    permanent,
+                    context.trump() == Some(c.suit()),
+                    c.rank(),
+                ))
+            };
+            strength(a).cmp(&strength(b))
+        }
+    }
+
+    #[test]
+    fn trumps_take_and_follow_each_other() {
+        use crate::common::cards::CardSet;
+
+        let two_of_clubs = ItalianCard::new(ItalianRank::Two, Suit::Clubs);
+        let context = HandContext::<TrumpGame>::new()
+            .with_trump(Suit::Hearts)
+            .with_permanent_trumps([two_of_clubs].into_iter().collect())
+            .with_contract(7);
+        assert_eq!(context.contract(), Some(7));
+
+        let play = |context: HandContext<TrumpGame>, cards: [(ItalianRank, Suit); 4]| {
+            let mut trick = OngoingTrick::with_context(PlayerId::new(0).unwrap(), context);
+            for (rank, suit) in cards {
+                trick.play_unchecked(ItalianCard::new(rank, suit));
+            }
+            *trick.finish().unwrap().taker()
+        };
+        let cards = [
+            (ItalianRank::Seven, Suit::Spades),
+            (ItalianRank::King, Suit::Spades),
+            (ItalianRank::Four, Suit::Hearts),
+            (ItalianRank::Five, Suit::Hearts),
+        ];
+        assert_eq!(play(HandContext::new(), cards), 1);
+        assert_eq!(play(context, cards), 3);
+        let mut cards = cards;
+        cards[1] = (ItalianRank::Two, Suit::Clubs);
+        assert_eq!(play(context, cards), 1);
+
+        // Permanent trumps follow the trump suit, and not their own suit.
+        let hand: CardSet<ItalianCard> = [
+            two_of_clubs,
+            ItalianCard::new(ItalianRank::Ace, Suit::Spades),
+        ]
+        .into_iter()
+        .collect();
+        let ace_of_hearts = ItalianCard::new(ItalianRank::Ace, Suit::Hearts);
+        let three_of_clubs = ItalianCard::new(ItalianRank::Three, Suit::Clubs);
+        assert_eq!(context.follow(&hand, Some(ace_of_hearts)).len(), 1);
+        assert_eq!(context.follow(&hand, Some(three_of_clubs)), hand);
+
+        let mut trick = OngoingTrick::<TrumpGame>::with_context(PlayerId::new(0).unwrap(), context);
+        trick.play_unchecked(ace_of_hearts);
+        assert!(TrumpGame::is_legal(&hand, &trick, two_of_clubs));
+        assert!(!TrumpGame::is_legal(
+            &hand,
+            &trick,
+            ItalianCard::new(ItalianRank::Ace, Suit::Spades)
+        ));
+
+        #[cfg(feature = "serde")]
+        {
+            let json = serde_json::to_string(&trick).unwrap();
+            let deserialized: OngoingTrick<TrumpGame> = serde_json::from_str(&json).unwrap();
+            assert_eq!(deserialized.context(), &context);
+        }
+    }
+
+    #[test]
+    fn permanent_trumps_are_ranked_by_the_game() {
+        let context = HandContext::<TrumpGame>::new()
+            .with_trump(Suit::Hearts)
+            .with_permanent_trumps(
+                [
+                    ItalianCard::new(ItalianRank::Two, Suit::Clubs),
+                    ItalianCard::new(ItalianRank::Two, Suit::Hearts),
+                ]
+                .into_iter()
+                .collect(),
+            );
+
+        let play = |cards: [(ItalianRank, Suit); 4]| {
+            let mut trick = OngoingTrick::with_context(PlayerId::new(0).unwrap(), context);
+            for (rank, suit) in cards {
+                trick.play_unchecked(ItalianCard::new(rank, suit));
+            }
+            *trick.finish().unwrap().taker()
+        };
+        let mut cards = [
+            (ItalianRank::Seven, Suit::Spades),
+            (ItalianRank::Two, Suit::Clubs),
+            (ItalianRank::Two, Suit::Hearts),
+            (ItalianRank::Ace, Suit::Hearts),
+        ];
+        assert_eq!(play(cards), 2);
+        cards.swap(1, 2);
+        assert_eq!(play(cards), 1);
+    }
+
+    /// A game with three players, where the last player to play takes.
+    #[derive(Clone, Copy, Debug)]
+    struct ThreePlayers {}
+
+    impl TrickTakingGame for ThreePlayers {
+        type CardType = ItalianCard;
+        type Contract = ();
+        type Bid = ();
+
+        const PLAYERS: usize = 3;
+
+        const TRICKS: usize = 13;
+
+        fn determine_taker(
+            cards: &[Self::CardType],
+            first_to_play: PlayerId<Self>,
+            _context: &HandContext<Self>,
+        ) -> PlayerId<Self> {
+            assert_eq!(cards.len(), Self::PLAYERS);
+            let mut last = first_to_play;
+            (0..Self::PLAYERS - 1).for_each(|_| last.inc());
+            last
+        }
+    }
+
+    #[test]
+    fn arity_follows_the_game() {
+        let mut trick = OngoingTrick::<ThreePlayers>::new(PlayerId::new(1).unwrap());
+        assert_eq!(trick.cards().len(), 3);
+        for rank in [ItalianRank::Ace, ItalianRank::Two, ItalianRank::Three] {
+            trick.play_unchecked(ItalianCard::new(rank, Suit::Spades));
+        }
+        assert_eq!(trick.next_to_play(), PlayerId::new(1).unwrap());
+
+        let trick = trick.finish().unwrap();
+        assert_eq!(trick.taker(), PlayerId::new(0).unwrap());
+        assert_eq!(trick.to_string(), "3S 1S 2S 0");
+        assert!(PlayerId::<ThreePlayers>::new(3).is_none());
+        assert_eq!(
+            super::OngoingHand::<ThreePlayers>::default().tricks().len(),
+            13
+        );
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn serde_round_trips_and_validates() {
+        use super::{OngoingHand, Trick};
+
+        let id: PlayerId<TestGame> = serde_json::from_str("3").unwrap();
+        assert_eq!(id, PlayerId::new(3).unwrap());
+        assert!(serde_json::from_str::<PlayerId<TestGame>>("4").is_err());
+
+        let mut ongoing_trick = OngoingTrick::<TestGame>::new(PlayerId::new(2).unwrap());
+        ongoing_trick.play_unchecked(ItalianCard::new(ItalianRank::Ace, Suit::Hearts));
+        let json = serde_json::to_string(&ongoing_trick).unwrap();
+        assert_eq!(json, r#"{"cards":[null,null,"1H",null],"first_to_play":2}"#);
+        let deserialized: OngoingTrick<TestGame> = serde_json::from_str(&json).unwrap();
+        assert_eq!(deserialized.next_to_play(), PlayerId::new(3).unwrap());
+
+        // Cards that haven't been played in turn are rejected.
+        let json = r#"{"cards":["1H",null,null,null],"first_to_play":2}"#;
+        assert!(serde_json::from_str::<OngoingTrick<TestGame>>(json).is_err());
+
+        let json = r#"{"cards":["1H","2H","3H","4H"],"taker":0}"#;
+        let trick: Trick<TestGame> = serde_json::from_str(json).unwrap();
+        assert_eq!(serde_json::to_string(&trick).unwrap(), json);
+
+        // The number of cards must be a number of players the game supports.
+        let json = r#"{"cards":["1H","2H","3H"],"taker":0}"#;
+        assert!(serde_json::from_str::<Trick<TestGame>>(json).is_err());
+
+        // Tricks store their number of players at runtime.
+        let json = r#"{"cards":["1H","2H","3H"],"taker":2}"#;
+        let trick: Trick<ThreePlayers> = serde_json::from_str(json).unwrap();
+        assert_eq!(trick.players(), 3);
+        assert!(
+            serde_json::from_str::<Trick<ThreePlayers>>(r#"{"cards":["1H","2H"],"taker":0}"#)
+                .is_err()
+        );
+
+        let ongoing_hand = OngoingHand::<TestGame>::new(PlayerId::new(1).unwrap());
+        let json = serde_json::to_string(&ongoing_hand).unwrap();
+        let deserialized: OngoingHand<TestGame> = serde_json::from_str(&json).unwrap();
+        assert_eq!(deserialized.tricks().len(), TestGame::TRICKS);
+        assert_eq!(deserialized.leader(0), PlayerId::new(1));
+
+        // The current trick must be led by the first player of the hand.
+        let json = json.replacen(r#""first_to_play":1"#, r#""first_to_play":2"#, 1);
+        assert!(serde_json::from_str::<OngoingHand<TestGame>>(&json).is_err());
+    }
 }
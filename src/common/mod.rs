@@ -1,4 +1,20 @@
+/// Auctions played before the tricks of a hand, to decide its contract.
+pub mod auction;
 /// Common cards and decks types.
 pub mod cards;
+/// Dealing of decks to players, following configurable patterns.
+pub mod dealer;
+/// Exchanges of cards between players before the first trick of a hand.
+pub mod exchange;
+/// A state machine that plays complete games, hand after hand.
+pub mod game;
 /// Common utility types to define tricks, hands, players.
 pub mod hands;
+/// Dealing shoes made of several decks, for casino games.
+pub mod shoe;
+/// Algorithms to shuffle decks of cards.
+pub mod shuffle;
+/// Stocks of undealt cards that players draw from after every trick.
+pub mod stock;
+/// Partnerships between players and the scores of each team.
+pub mod teams;
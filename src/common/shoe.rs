@@ -0,0 +1,233 @@
+use rand::Rng;
+
+use super::{
+    cards::{Card, Deck, DeckBuilder},
+    shuffle::{FisherYates, Shuffler},
+};
+
+/// A dealing shoe, as used in casino games: several copies of a deck shuffled
+/// together, with a cut card that signals when the shoe has to be reshuffled.
+///
+/// Cards are drawn from the top of the shoe. Once the round is over, the
+/// cards that have been used are returned to the shoe with `discard`, and are
+/// kept aside until the next reshuffle. The cut card is placed at a given
+/// number of cards from the top: after that many cards have been dealt,
+/// `needs_reshuffle` returns true, and the game is expected to reshuffle the
+/// shoe at the end of the round.
+///
+/// # Examples
+///
+/// ```
+/// use rand::SeedableRng;
+/// use rand_chacha::ChaCha8Rng;
+/// use shuftlib::common::{cards::Deck, shoe::Shoe};
+///
+/// let mut rng = ChaCha8Rng::seed_from_u64(0);
+/// let mut shoe = Shoe::new(Deck::french(), 6);
+/// shoe.shuffle_with(&mut rng);
+/// shoe.place_cut_card_at(0.75);
+/// shoe.burn();
+///
+/// while !shoe.needs_reshuffle() {
+///     let hand = [shoe.draw().unwrap(), shoe.draw().unwrap()];
+///     shoe.discard(hand);
+/// }
+/// assert!(shoe.penetration() >= 0.75);
+///
+/// shoe.shuffle_with(&mut rng);
+/// assert_eq!(shoe.remaining(), 6 * 52);
+/// ```
+#[derive(Debug, Clone)]
+pub struct Shoe<T: Card> {
+    cards: Vec<T>,
+    discards: Vec<T>,
+    burned: Vec<T>,
+    size: usize,
+    cut: usize,
+}
+
+impl<T: Card> Shoe<T> {
+    /// Creates a shoe containing the given number of copies of `deck`. The
+    /// cards are not shuffled and the cut card is placed at the bottom of the
+    /// shoe.
+    pub fn new(deck: Deck<T>, decks: usize) -> Self {
+        let cards = DeckBuilder::new(deck).copies(decks).build().to_vec();
+        let size = cards.len();
+
+        Self {
+            cards,
+            discards: Vec::new(),
+            burned: Vec::new(),
+            size,
+            cut: size,
+        }
+    }
+
+    /// Collects every card that has been discarded or burned and shuffles it
+    /// back into the shoe with the Fisher–Yates algorithm. The cut card stays
+    /// at the same position. Cards that have been drawn but not discarded yet
+    /// are not part of the new shoe.
+    pub fn shuffle_with<R: Rng + ?Sized>(&mut self, rng: &mut R) {
+        self.shuffle_by(&FisherYates, rng);
+    }
+
+    /// Same as `shuffle_with`, but with the given shuffling algorithm.
+    pub fn shuffle_by<S: Shuffler, R: Rng + ?Sized>(&mut self, shuffler: &S, rng: &mut R) {
+        self.cards.append(&mut self.discards);
+        self.cards.append(&mut self.burned);
+        shuffler.shuffle(&mut self.cards, rng);
+    }
+
+    /// Places the cut card so that it's reached after `position` cards have
+    /// been dealt from a full shoe. Values greater than the size of the shoe
+    /// place it at the bottom.
+    pub fn place_cut_card(&mut self, position: usize) {
+        self.cut = position.min(self.size);
+    }
+
+    /// Places the cut card so that it's reached when the given fraction of the
+    /// shoe has been dealt: 0.75 means that a quarter of the shoe won't be
+    /// played. The value is clamped to the range 0..=1.
+    pub fn place_cut_card_at(&mut self, penetration: f64) {
+        let position = (self.size as f64 * penetration.clamp(0.0, 1.0)).round();
+        self.place_cut_card(position as usize);
+    }
+
+    /// The number of cards dealt from a full shoe after which the cut card is
+    /// reached.
+    pub fn cut_card(&self) -> usize {
+        self.cut
+    }
+
+    /// Draws the top-most card of the shoe. It returns None if there are no
+    /// cards left.
+    pub fn draw(&mut self) -> Option<T> {
+        self.cards.pop()
+    }
+
+    /// Removes the top-most card of the shoe without using it. Burned cards
+    /// count towards the penetration and are shuffled back with the discards.
+    pub fn burn(&mut self) -> Option<T> {
+        let card = self.cards.pop()?;
+        self.burned.push(card);
+        Some(card)
+    }
+
+    /// Returns cards that have been used to the shoe. They are kept in the
+    /// discard tray until the next shuffle.
+    pub fn discard<I: IntoIterator<Item = T>>(&mut self, cards: I) {
+        self.discards.extend(cards);
+    }
+
+    /// Whether the cut card has been reached, meaning that the shoe should be
+    /// reshuffled at the end of the current round.
+    pub fn needs_reshuffle(&self) -> bool {
+        self.dealt() >= self.cut
+    }
+
+    /// The fraction of the full shoe that has been dealt or burned, in the
+    /// range 0..=1.
+    pub fn penetration(&self) -> f64 {
+        if self.size == 0 {
+            return 0.0;
+        }
+
+        self.dealt() as f64 / self.size as f64
+    }
+
+    /// The number of cards dealt or burned since the last shuffle.
+    pub fn dealt(&self) -> usize {
+        self.size - self.cards.len()
+    }
+
+    /// The number of cards left in the shoe.
+    pub fn remaining(&self) -> usize {
+        self.cards.len()
+    }
+
+    /// The number of cards in a full shoe.
+    pub fn size(&self) -> usize {
+        self.size
+    }
+
+    /// The cards that have been discarded since the last shuffle, in the order
+    /// they have been discarded.
+    pub fn discards(&self) -> &[T] {
+        &self.discards
+    }
+
+    /// The cards that have been burned since the last shuffle.
+    pub fn burned(&self) -> &[T] {
+        &self.burned
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use rand::SeedableRng;
+    use rand_chacha::ChaCha8Rng;
+
+    use crate::common::cards::{Card, CardSet, Deck, ItalianCard};
+
+    use super::Shoe;
+
+    #[test]
+    fn cut_card_signals_reshuffle() {
+        let mut shoe = Shoe::new(Deck::italian(), 2);
+        shoe.shuffle_with(&mut ChaCha8Rng::seed_from_u64(0));
+        shoe.place_cut_card_at(0.5);
+        assert_eq!(shoe.cut_card(), 40);
+
+        assert!(shoe.burn().is_some());
+        for _ in 0..38 {
+            let card = shoe.draw().unwrap();
+            assert!(!shoe.needs_reshuffle());
+            shoe.discard([card]);
+        }
+        assert!(shoe.draw().is_some());
+        assert!(shoe.needs_reshuffle());
+        assert!((shoe.penetration() - 0.5).abs() < f64::EPSILON);
+        assert_eq!(shoe.discards().len(), 38);
+        assert_eq!(shoe.burned().len(), 1);
+    }
+
+    #[test]
+    fn shuffle_collects_discards() {
+        let mut shoe = Shoe::new(Deck::italian(), 3);
+        let mut rng = ChaCha8Rng::seed_from_u64(0);
+        shoe.place_cut_card(200);
+        assert_eq!(shoe.cut_card(), 120);
+
+        let in_play = shoe.draw().unwrap();
+        while let Some(card) = shoe.draw() {
+            shoe.discard([card]);
+        }
+        assert!(shoe.needs_reshuffle());
+
+        shoe.shuffle_with(&mut rng);
+        assert_eq!(shoe.remaining(), 119);
+        shoe.discard([in_play]);
+        shoe.shuffle_with(&mut rng);
+        assert_eq!(shoe.remaining(), 120);
+        assert!(!shoe.needs_reshuffle());
+
+        let mut shoe_cards = shoe.cards.clone();
+        let set: CardSet<ItalianCard> = shoe_cards.iter().copied().collect();
+        assert_eq!(set.len(), ItalianCard::DECK_SIZE);
+        shoe_cards.sort_by_key(|c| c.index());
+        assert!(shoe_cards
+            .chunks(3)
+            .all(|copies| copies.iter().all(|&c| c == copies[0])));
+    }
+
+    #[test]
+    fn empty_shoe_works() {
+        let mut shoe = Shoe::new(Deck::<ItalianCard>::new(), 4);
+        shoe.shuffle_with(&mut ChaCha8Rng::seed_from_u64(0));
+        assert_eq!(shoe.draw(), None);
+        assert_eq!(shoe.burn(), None);
+        assert!(shoe.penetration().abs() < f64::EPSILON);
+        assert!(shoe.needs_reshuffle());
+    }
+}
@@ -0,0 +1,239 @@
+use rand::Rng;
+
+/// An algorithm used to permute the cards of a deck. Besides the default
+/// `FisherYates`, which produces every permutation with the same probability,
+/// this module contains models of the shuffles performed by hand, which are
+/// useful to simulate the imperfect randomness of real games.
+///
+/// The top of the deck is the end of the slice, since that's where
+/// `Deck::draw` takes cards from. Every implementation must accept empty and
+/// one-card slices.
+pub trait Shuffler {
+    /// Permutes `cards` in place, drawing random numbers from `rng`.
+    fn shuffle<T: Copy, R: Rng + ?Sized>(&self, cards: &mut [T], rng: &mut R);
+}
+
+/// Returns a random index in the range `0..bound`. Unlike `Rng::gen_range`,
+/// the values returned for a given sequence of random numbers are fixed by
+/// this crate, so that seeded shuffles don't change when `rand` is updated.
+///
+/// # Panics
+///
+/// Panics if `bound` is 0.
+pub(crate) fn random_index<R: Rng + ?Sized>(rng: &mut R, bound: usize) -> usize {
+    let bound = bound as u64;
+    // Rejection sampling over the largest multiple of `bound` representable
+    // in a u64, to avoid the modulo bias.
+    let zone = u64::MAX - (u64::MAX - bound + 1) % bound;
+    loop {
+        let value = rng.next_u64();
+        if value <= zone {
+            return (value % bound) as usize;
+        }
+    }
+}
+
+/// The Fisher–Yates (or Knuth) shuffle: a single pass over the cards that
+/// produces every permutation with the same probability. This is the
+/// algorithm used by `Deck::shuffle`.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct FisherYates;
+
+impl Shuffler for FisherYates {
+    fn shuffle<T: Copy, R: Rng + ?Sized>(&self, cards: &mut [T], rng: &mut R) {
+        for i in (1..cards.len()).rev() {
+            let j = random_index(rng, i + 1);
+            cards.swap(i, j);
+        }
+    }
+}
+
+/// The riffle shuffle, following the Gilbert–Shannon–Reeds model: the deck is
+/// cut in two packets with a binomially distributed size, then the cards are
+/// dropped one at a time from either packet, with a probability proportional
+/// to the number of cards left in it.
+#[derive(Debug, Clone, Copy)]
+pub struct Riffle {
+    times: usize,
+}
+
+impl Riffle {
+    /// Creates a riffle shuffle repeated the given number of times.
+    pub fn new(times: usize) -> Self {
+        Self { times }
+    }
+}
+
+impl Default for Riffle {
+    /// Seven riffles, which are known to be enough to mix a 52 cards deck.
+    fn default() -> Self {
+        Self::new(7)
+    }
+}
+
+impl Shuffler for Riffle {
+    fn shuffle<T: Copy, R: Rng + ?Sized>(&self, cards: &mut [T], rng: &mut R) {
+        let mut packets = Vec::with_capacity(cards.len());
+        for _ in 0..self.times {
+            let cut = (0..cards.len()).filter(|_| rng.next_u32() & 1 == 1).count();
+            packets.clear();
+            packets.extend_from_slice(cards);
+            let (mut left, mut right) = packets.split_at(cut);
+
+            for card in cards.iter_mut() {
+                let from_left = random_index(rng, left.len() + right.len()) < left.len();
+                let packet = if from_left { &mut left } else { &mut right };
+                if let Some((&first, rest)) = packet.split_first() {
+                    *card = first;
+                    *packet = rest;
+                }
+            }
+        }
+    }
+}
+
+/// The overhand shuffle: small packets of cards are repeatedly taken from the
+/// top of the deck and dropped on a new pile, which reverses the order of the
+/// packets but not the order of the cards inside each of them. It mixes
+/// the cards much more slowly than a riffle.
+#[derive(Debug, Clone, Copy)]
+pub struct Overhand {
+    times: usize,
+    average_packet: usize,
+}
+
+impl Overhand {
+    /// Creates an overhand shuffle repeated the given number of times, where
+    /// the packets have the given average size. The size of each packet is
+    /// uniformly distributed between 1 and `2 * average_packet - 1`.
+    pub fn new(times: usize, average_packet: usize) -> Self {
+        Self {
+            times,
+            average_packet: average_packet.max(1),
+        }
+    }
+}
+
+impl Default for Overhand {
+    fn default() -> Self {
+        Self::new(10, 4)
+    }
+}
+
+impl Shuffler for Overhand {
+    fn shuffle<T: Copy, R: Rng + ?Sized>(&self, cards: &mut [T], rng: &mut R) {
+        let mut pile = Vec::with_capacity(cards.len());
+        for _ in 0..self.times {
+            pile.clear();
+            let mut remaining = cards.len();
+            while remaining > 0 {
+                let size = (1 + random_index(rng, 2 * self.average_packet - 1)).min(remaining);
+                // The top of the deck is the end of the slice: each packet
+                // keeps its order and ends up on top of the ones taken before.
+                pile.extend_from_slice(&cards[remaining - size..remaining]);
+                remaining -= size;
+            }
+            cards.copy_from_slice(&pile);
+        }
+    }
+}
+
+/// A single cut: a uniformly chosen number of cards is taken from the top of
+/// the deck and placed at the bottom. It doesn't mix the deck, but it moves
+/// every card to a uniformly distributed position.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Cut;
+
+impl Shuffler for Cut {
+    fn shuffle<T: Copy, R: Rng + ?Sized>(&self, cards: &mut [T], rng: &mut R) {
+        if cards.is_empty() {
+            return;
+        }
+
+        let top = random_index(rng, cards.len());
+        cards.rotate_right(top);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::SeedableRng;
+    use rand_chacha::ChaCha8Rng;
+
+    use super::{Cut, FisherYates, Overhand, Riffle, Shuffler};
+
+    const CARDS: usize = 6;
+    const TRIALS: usize = 30_000;
+    /// The critical value of the chi-square distribution with (6 - 1)^2 = 25
+    /// degrees of freedom, for a significance level of 0.001.
+    const CRITICAL_VALUE: f64 = 52.62;
+
+    /// A shuffler that leaves the cards where they are.
+    struct Identity;
+
+    impl Shuffler for Identity {
+        fn shuffle<T: Copy, R: rand::Rng + ?Sized>(&self, _cards: &mut [T], _rng: &mut R) {}
+    }
+
+    /// Shuffles a small deck many times and computes the chi-square statistic
+    /// of the table counting how many times each card ended up in each
+    /// position, against the uniform distribution.
+    fn position_chi_square<S: Shuffler>(shuffler: &S) -> f64 {
+        let mut rng = ChaCha8Rng::seed_from_u64(0);
+        let mut counts = [[0usize; CARDS]; CARDS];
+        for _ in 0..TRIALS {
+            let mut cards: [usize; CARDS] = std::array::from_fn(|i| i);
+            shuffler.shuffle(&mut cards, &mut rng);
+            for (position, &card) in cards.iter().enumerate() {
+                counts[card][position] += 1;
+            }
+        }
+
+        let expected = TRIALS as f64 / CARDS as f64;
+        counts
+            .iter()
+            .flatten()
+            .map(|&observed| (observed as f64 - expected).powi(2) / expected)
+            .sum()
+    }
+
+    fn is_permutation<S: Shuffler>(shuffler: &S, len: usize) -> bool {
+        let mut rng = ChaCha8Rng::seed_from_u64(1);
+        let mut cards: Vec<usize> = (0..len).collect();
+        shuffler.shuffle(&mut cards, &mut rng);
+        cards.sort_unstable();
+        cards == (0..len).collect::<Vec<_>>()
+    }
+
+    #[test]
+    fn shufflers_produce_permutations() {
+        for len in [0, 1, 2, 3, 40, 52] {
+            assert!(is_permutation(&FisherYates, len));
+            assert!(is_permutation(&Riffle::default(), len));
+            assert!(is_permutation(&Overhand::default(), len));
+            assert!(is_permutation(&Cut, len));
+        }
+    }
+
+    #[test]
+    fn chi_square_detects_bias() {
+        assert!(position_chi_square(&Identity) > CRITICAL_VALUE);
+        assert!(position_chi_square(&Riffle::new(1)) > CRITICAL_VALUE);
+        assert!(position_chi_square(&Overhand::new(1, 2)) > CRITICAL_VALUE);
+    }
+
+    #[test]
+    fn fisher_yates_is_uniform() {
+        assert!(position_chi_square(&FisherYates) < CRITICAL_VALUE);
+    }
+
+    #[test]
+    fn repeated_riffles_are_uniform() {
+        assert!(position_chi_square(&Riffle::new(10)) < CRITICAL_VALUE);
+    }
+
+    #[test]
+    fn cut_positions_are_uniform() {
+        assert!(position_chi_square(&Cut) < CRITICAL_VALUE);
+    }
+}
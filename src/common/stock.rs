@@ -0,0 +1,234 @@
+use super::{
+    cards::{Card, Deck},
+    hands::{Player, PlayerId, TrickTakingGame, MAX_PLAYERS},
+};
+use crate::error::ShuftError;
+
+/// The cards that haven't been dealt, which the players draw from after every
+/// trick to refill their hands, like in briscola or in tressette in two. The
+/// taker of a trick draws first, then the other players in turn. A card can
+/// be turned face up at the bottom of the stock, usually to show the trump
+/// suit: it stays visible to every player and it's the last card drawn, so
+/// in two players it goes to the one who lost the last trick of the stock.
+///
+/// # Examples
+///
+/// ```
+/// use shuftlib::common::{cards::Deck, hands::{Player, PlayerId}, stock::Stock};
+/// use shuftlib::tressette::TressetteRules;
+///
+/// let mut players: Vec<Player<TressetteRules>> = (0..2)
+///     .map(|i| Player::new(PlayerId::with_players(i, 2).unwrap()))
+///     .collect();
+/// let mut deck = Deck::tressette();
+/// deck.drain(..36);
+/// let (bottom, top) = (deck[0], deck[3]);
+///
+/// // Four cards are left: the top one is turned face up.
+/// let mut stock = Stock::new(deck).turn_up();
+/// assert_eq!(stock.face_up(), Some(top));
+///
+/// // The taker draws first, the other player takes the face up card.
+/// let taker = PlayerId::with_players(1, 2).unwrap();
+/// stock.refill(&mut players, taker).unwrap();
+/// stock.refill(&mut players, taker).unwrap();
+/// assert!(stock.is_empty());
+/// assert_eq!(stock.drawn(1), Some(bottom));
+/// assert_eq!(stock.drawn(0), Some(top));
+/// ```
+#[derive(Debug, Clone, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Stock<C: Card> {
+    cards: Deck<C>,
+    face_up: Option<C>,
+    drawn: [Option<C>; MAX_PLAYERS],
+}
+
+impl<C: Card> Stock<C> {
+    /// Creates a stock made of the cards of `deck`, face down, with the top of
+    /// the deck as the top of the stock.
+    pub fn new(deck: Deck<C>) -> Self {
+        Self {
+            cards: deck,
+            face_up: None,
+            drawn: [None; MAX_PLAYERS],
+        }
+    }
+
+    /// Turns the top card face up and puts it at the bottom of the stock.
+    pub fn turn_up(mut self) -> Self {
+        if let Some(card) = self.cards.draw() {
+            if let Some(previous) = self.face_up.replace(card) {
+                self.cards.insert(0, previous);
+            }
+        }
+        self
+    }
+
+    /// The card turned face up at the bottom of the stock, until it's drawn.
+    pub fn face_up(&self) -> Option<C> {
+        self.face_up
+    }
+
+    /// The number of cards left, including the face up one.
+    pub fn len(&self) -> usize {
+        self.cards.len() + usize::from(self.face_up.is_some())
+    }
+
+    /// Whether every card has been drawn.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Draws the top card of the stock, or the face up card once the other
+    /// ones are over. It returns None if the stock is empty.
+    pub fn draw(&mut self) -> Option<C> {
+        self.cards.draw().or_else(|| self.face_up.take())
+    }
+
+    /// The card drawn by the player at seat `seat` in the last refill, if
+    /// any. In some games, like tressette in two, drawn cards are shown to
+    /// every player.
+    pub fn drawn(&self, seat: usize) -> Option<C> {
+        self.drawn.get(seat).copied().flatten()
+    }
+
+    /// Gives one card to each player, starting from `taker` and going on
+    /// with the players after them. Nothing is drawn from an empty stock.
+    ///
+    /// # Errors
+    ///
+    /// Fails if `players` doesn't contain exactly one player for each id, if
+    /// there aren't enough cards for every player or if a player would
+    /// receive a card they already hold. In all these cases no card is
+    /// drawn.
+    pub fn refill<G>(
+        &mut self,
+        players: &mut [Player<G>],
+        taker: PlayerId<G>,
+    ) -> Result<(), ShuftError>
+    where
+        G: TrickTakingGame<CardType = C>,
+    {
+        let seats = taker.players();
+        if players.len() != seats {
+            return Err(ShuftError::InvalidPlayers {
+                found: players.len(),
+                expected: seats,
+            });
+        }
+        if self.is_empty() {
+            self.drawn = [None; MAX_PLAYERS];
+            return Ok(());
+        }
+        if self.len() < seats {
+            return Err(ShuftError::NotEnoughCards {
+                needed: seats,
+                available: self.len(),
+            });
+        }
+
+        // The cards are drawn from a copy of the stock, so that nothing
+        // changes if a player would receive a duplicate.
+        let mut stock = self.clone();
+        stock.drawn = [None; MAX_PLAYERS];
+        for seat in (0..seats).map(|i| (*taker + i) % seats) {
+            let card = stock.draw().ok_or(ShuftError::NotEnoughCards {
+                needed: seats,
+                available: self.len(),
+            })?;
+            if players[seat].hand().contains(card) {
+                return Err(ShuftError::DuplicateCard(card.to_string()));
+            }
+            stock.drawn[seat] = Some(card);
+        }
+
+        for (player, card) in players.iter_mut().zip(stock.drawn) {
+            if let Some(card) = card {
+                player.give(card);
+            }
+        }
+        *self = stock;
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use proptest::prelude::*;
+
+    use crate::{
+        common::{
+            cards::Deck,
+            hands::{Player, PlayerId},
+        },
+        error::ShuftError,
+        tressette::TressetteRules,
+    };
+
+    use super::Stock;
+
+    fn players(count: usize) -> Vec<Player<TressetteRules>> {
+        (0..count)
+            .map(|i| Player::new(PlayerId::with_players(i, count).unwrap()))
+            .collect()
+    }
+
+    #[test]
+    fn refills_need_a_card_for_everyone() {
+        let mut players = players(4);
+        let mut deck = Deck::tressette();
+        deck.drain(..37);
+        let mut stock = Stock::new(deck).turn_up();
+        let taker = PlayerId::new(2).unwrap();
+
+        assert_eq!(
+            stock.refill(&mut players, taker),
+            Err(ShuftError::NotEnoughCards {
+                needed: 4,
+                available: 3
+            })
+        );
+        assert_eq!(stock.len(), 3);
+        assert!(players.iter().all(|p| p.hand().is_empty()));
+
+        assert_eq!(
+            stock.refill(&mut players[..2], taker),
+            Err(ShuftError::InvalidPlayers {
+                found: 2,
+                expected: 4
+            })
+        );
+    }
+
+    proptest! {
+        #[test]
+        fn every_card_is_drawn_once(seed: u64, taker in 0usize..4, turn_up: bool) {
+            let mut players = players(4);
+            let mut deck = Deck::tressette();
+            deck.shuffle_seeded(seed);
+            let mut stock = Stock::new(deck);
+            if turn_up {
+                stock = stock.turn_up();
+            }
+            let face_up = stock.face_up();
+            let taker = PlayerId::new(taker).unwrap();
+
+            while !stock.is_empty() {
+                stock.refill(&mut players, taker).unwrap();
+                prop_assert!(stock.drawn(*taker).is_some());
+            }
+
+            prop_assert!(players.iter().all(|p| p.hand().len() == 10));
+            if let Some(card) = face_up {
+                // The face up card is the last one, drawn by the player
+                // before the taker.
+                prop_assert_eq!(stock.drawn((*taker + 3) % 4), Some(card));
+            }
+            stock.refill(&mut players, taker).unwrap();
+            prop_assert_eq!(stock.drawn(*taker), None);
+        }
+    }
+}
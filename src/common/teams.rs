@@ -0,0 +1,225 @@
+use std::fmt::Display;
+
+/// How the players of a hand are grouped into teams. Teams are identified by
+/// an index, starting from 0, and players by their seat, which is the value
+/// of their `PlayerId`.
+///
+/// # Examples
+///
+/// ```
+/// use shuftlib::common::teams::Teams;
+///
+/// // Tressette in four: players 0 and 2 play against players 1 and 3.
+/// let teams = Teams::Partnerships(2);
+/// assert_eq!(teams.team(2), 0);
+/// assert_eq!(teams.team(3), 1);
+/// assert!(teams.are_partners(1, 3));
+///
+/// // Skat: the declarer plays against the other two players.
+/// let teams = Teams::Solo(1);
+/// assert_eq!(teams.members(1, 3), vec![0, 2]);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Teams {
+    /// Every player plays for themselves: the team of a player is their seat.
+    #[default]
+    Individual,
+    /// Fixed partnerships of players sitting in alternate seats: with the
+    /// given number of teams, the player at seat `s` plays for team
+    /// `s % teams`.
+    Partnerships(usize),
+    /// The player at the given seat plays alone, as team 0, against the
+    /// coalition of all the other players, as team 1.
+    Solo(usize),
+    /// The declarer plays together with the partner they called, as team 0,
+    /// against the other players, as team 1. The partner is None until they
+    /// are revealed, and in the meantime the declarer plays alone.
+    CalledPartner {
+        /// The seat of the declarer.
+        declarer: usize,
+        /// The seat of the called partner, if known.
+        partner: Option<usize>,
+    },
+}
+
+impl Teams {
+    /// The team of the player at seat `seat`.
+    pub fn team(&self, seat: usize) -> usize {
+        match *self {
+            Teams::Individual => seat,
+            Teams::Partnerships(teams) => seat % teams.max(1),
+            Teams::Solo(declarer) => usize::from(seat != declarer),
+            Teams::CalledPartner { declarer, partner } => {
+                usize::from(seat != declarer && Some(seat) != partner)
+            }
+        }
+    }
+
+    /// The number of teams of a hand played by `players` players.
+    pub fn count(&self, players: usize) -> usize {
+        match *self {
+            Teams::Individual => players,
+            Teams::Partnerships(teams) => teams.max(1).min(players),
+            Teams::Solo(_) | Teams::CalledPartner { .. } => 2,
+        }
+    }
+
+    /// The seats of the players of `team`, in a hand played by `players`
+    /// players.
+    pub fn members(&self, team: usize, players: usize) -> Vec<usize> {
+        (0..players)
+            .filter(|&seat| self.team(seat) == team)
+            .collect()
+    }
+
+    /// Whether the players at seats `a` and `b` play for the same team.
+    pub fn are_partners(&self, a: usize, b: usize) -> bool {
+        self.team(a) == self.team(b)
+    }
+}
+
+/// The scores accumulated by each team during a game, indexed by team. Teams
+/// that haven't scored yet have a score of 0, so the default scoreboard can be
+/// used by any number of teams.
+///
+/// # Examples
+///
+/// ```
+/// use shuftlib::common::teams::{Scoreboard, Teams};
+///
+/// let teams = Teams::Partnerships(2);
+/// let mut scoreboard = Scoreboard::default();
+/// scoreboard.add_for(&teams, 3, 5);
+/// scoreboard.add(0, 2);
+///
+/// assert_eq!(scoreboard.scores(), [2, 5]);
+/// assert_eq!(scoreboard.leader(), Some(1));
+/// assert_eq!(scoreboard.to_string(), "2 - 5");
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Default, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Scoreboard {
+    scores: Vec<i32>,
+}
+
+impl Scoreboard {
+    /// Creates a scoreboard where each one of the given number of teams has a
+    /// score of 0.
+    pub fn new(teams: usize) -> Self {
+        Self {
+            scores: vec![0; teams],
+        }
+    }
+
+    /// The score of `team`.
+    pub fn score(&self, team: usize) -> i32 {
+        self.scores.get(team).copied().unwrap_or_default()
+    }
+
+    /// The scores of the teams, indexed by team.
+    pub fn scores(&self) -> &[i32] {
+        &self.scores
+    }
+
+    /// Adds `points` to the score of `team`. Points can be negative, for games
+    /// where they are penalties.
+    pub fn add(&mut self, team: usize, points: i32) {
+        if team >= self.scores.len() {
+            self.scores.resize(team + 1, 0);
+        }
+        self.scores[team] += points;
+    }
+
+    /// Adds `points` to the team of the player at seat `seat`.
+    pub fn add_for(&mut self, teams: &Teams, seat: usize, points: i32) {
+        self.add(teams.team(seat), points);
+    }
+
+    /// The team with the highest score, or None if there's a tie for the
+    /// highest score.
+    pub fn leader(&self) -> Option<usize> {
+        let (leader, &best) = self
+            .scores
+            .iter()
+            .enumerate()
+            .max_by_key(|(_, &score)| score)?;
+        let ties = self.scores.iter().filter(|&&score| score == best).count();
+
+        (ties == 1).then_some(leader)
+    }
+
+    /// The sum of the scores of every team.
+    pub fn total(&self) -> i32 {
+        self.scores.iter().sum()
+    }
+}
+
+impl From<Vec<i32>> for Scoreboard {
+    fn from(scores: Vec<i32>) -> Self {
+        Self { scores }
+    }
+}
+
+impl Display for Scoreboard {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for (i, score) in self.scores.iter().enumerate() {
+            if i > 0 {
+                write!(f, " - ")?;
+            }
+            write!(f, "{}", score)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use proptest::prelude::*;
+
+    use super::{Scoreboard, Teams};
+
+    #[test]
+    fn called_partners_join_the_declarer() {
+        let mut teams = Teams::CalledPartner {
+            declarer: 2,
+            partner: None,
+        };
+        assert_eq!(teams.members(0, 5), vec![2]);
+
+        teams = Teams::CalledPartner {
+            declarer: 2,
+            partner: Some(4),
+        };
+        assert_eq!(teams.members(0, 5), vec![2, 4]);
+        assert_eq!(teams.members(1, 5), vec![0, 1, 3]);
+        assert_eq!(teams.count(5), 2);
+    }
+
+    #[test]
+    fn ties_have_no_leader() {
+        assert_eq!(Scoreboard::default().leader(), None);
+        assert_eq!(Scoreboard::from(vec![3, 1, 3]).leader(), None);
+        assert_eq!(Scoreboard::from(vec![3, 1, 4]).leader(), Some(2));
+        assert_eq!(Scoreboard::new(2).score(5), 0);
+    }
+
+    proptest! {
+        #[test]
+        fn every_player_has_a_team(players in 2usize..=8, seat in 0usize..8, teams in 1usize..=4) {
+            let seat = seat % players;
+            for teams in [Teams::Individual, Teams::Partnerships(teams), Teams::Solo(0)] {
+                prop_assert!(teams.team(seat) < teams.count(players));
+                prop_assert!(teams.members(teams.team(seat), players).contains(&seat));
+            }
+        }
+
+        #[test]
+        fn points_go_to_the_team(scores in prop::collection::vec(-50i32..50, 1..6), seat in 0usize..8, points in -10i32..10) {
+            let mut scoreboard = Scoreboard::from(scores.clone());
+            scoreboard.add_for(&Teams::Individual, seat, points);
+            prop_assert_eq!(scoreboard.score(seat), scores.get(seat).copied().unwrap_or_default() + points);
+            prop_assert_eq!(scoreboard.total(), scores.iter().sum::<i32>() + points);
+        }
+    }
+}
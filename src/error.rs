@@ -0,0 +1,186 @@
+use std::{error::Error, fmt::Display};
+
+use crate::common::{
+    auction::CallError,
+    cards::ParseCardError,
+    exchange::ExchangeError,
+    hands::{DeclarationError, PlayError},
+};
+use crate::tressette::SignalError;
+
+/// The errors returned by the fallible operations of this crate.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ShuftError {
+    /// A player id is out of the range of the players of the game.
+    InvalidPlayerId {
+        /// The id that was requested.
+        id: usize,
+        /// The number of players of the game.
+        players: usize,
+    },
+    /// The number of players isn't supported by the game, or doesn't match
+    /// the one of the game being played.
+    InvalidPlayers {
+        /// The number of players that was given.
+        found: usize,
+        /// The number of players of the game.
+        expected: usize,
+    },
+    /// A trick can't be finished, because not every player played a card.
+    IncompleteTrick,
+    /// A hand can't be finished, because not every trick has been played.
+    IncompleteHand,
+    /// No more cards can be played in a hand, because every trick has
+    /// already been played.
+    HandComplete,
+    /// A trick is added to a hand out of order, or in place of a trick that
+    /// has already been played.
+    UnexpectedTrick {
+        /// The index of the trick that was added.
+        id: usize,
+        /// The index of the trick being played.
+        expected: usize,
+    },
+    /// The same card would end up twice in a place that can only hold one
+    /// copy of it, like the hand of a player.
+    DuplicateCard(String),
+    /// There aren't enough cards left to complete the operation.
+    NotEnoughCards {
+        /// The number of cards needed.
+        needed: usize,
+        /// The number of cards available.
+        available: usize,
+    },
+    /// A card can't be played.
+    IllegalPlay(PlayError),
+    /// A combination of cards can't be declared.
+    IllegalDeclaration(DeclarationError),
+    /// A signal can't be given in a game of tressette.
+    IllegalSignal(SignalError),
+    /// A call can't be made in an auction.
+    IllegalCall(CallError),
+    /// An auction can't be finished, because it's not over yet.
+    IncompleteAuction,
+    /// The cards selected for an exchange can't be exchanged.
+    IllegalExchange(ExchangeError),
+    /// An exchange can't be applied, or cards can't be played, because not
+    /// every player selected the cards to exchange yet.
+    IncompleteExchange,
+    /// The game is over, so no more moves can be made.
+    GameCompleted,
+    /// A card can't be parsed.
+    Parse(ParseCardError),
+}
+
+impl Display for ShuftError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ShuftError::InvalidPlayerId { id, players } => write!(
+                f,
+                "invalid player id {}, expected a value in range 0..{}",
+                id, players
+            ),
+            ShuftError::InvalidPlayers { found, expected } => write!(
+                f,
+                "invalid number of players {}, expected {}",
+                found, expected
+            ),
+            ShuftError::IncompleteTrick => write!(f, "not every player played in the trick"),
+            ShuftError::IncompleteHand => write!(f, "not every trick of the hand has been played"),
+            ShuftError::HandComplete => write!(f, "every trick of the hand has been played"),
+            ShuftError::UnexpectedTrick { id, expected } => {
+                write!(f, "unexpected trick {}, expected trick {}", id, expected)
+            }
+            ShuftError::DuplicateCard(card) => write!(f, "duplicate card: {}", card),
+            ShuftError::NotEnoughCards { needed, available } => write!(
+                f,
+                "{} cards are needed, but only {} are available",
+                needed, available
+            ),
+            ShuftError::IllegalPlay(e) => write!(f, "illegal play: {}", e),
+            ShuftError::IllegalDeclaration(e) => write!(f, "illegal declaration: {}", e),
+            ShuftError::IllegalSignal(e) => write!(f, "illegal signal: {}", e),
+            ShuftError::IllegalCall(e) => write!(f, "illegal call: {}", e),
+            ShuftError::IncompleteAuction => write!(f, "the auction is not over"),
+            ShuftError::IllegalExchange(e) => write!(f, "illegal exchange: {}", e),
+            ShuftError::IncompleteExchange => {
+                write!(f, "not every player selected the cards to exchange")
+            }
+            ShuftError::GameCompleted => write!(f, "the game is over"),
+            ShuftError::Parse(e) => write!(f, "parse error: {}", e),
+        }
+    }
+}
+
+impl Error for ShuftError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            ShuftError::IllegalPlay(e) => Some(e),
+            ShuftError::IllegalDeclaration(e) => Some(e),
+            ShuftError::IllegalSignal(e) => Some(e),
+            ShuftError::IllegalCall(e) => Some(e),
+            ShuftError::IllegalExchange(e) => Some(e),
+            ShuftError::Parse(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+impl From<PlayError> for ShuftError {
+    fn from(value: PlayError) -> Self {
+        ShuftError::IllegalPlay(value)
+    }
+}
+
+impl From<DeclarationError> for ShuftError {
+    fn from(value: DeclarationError) -> Self {
+        ShuftError::IllegalDeclaration(value)
+    }
+}
+
+impl From<SignalError> for ShuftError {
+    fn from(value: SignalError) -> Self {
+        ShuftError::IllegalSignal(value)
+    }
+}
+
+impl From<CallError> for ShuftError {
+    fn from(value: CallError) -> Self {
+        ShuftError::IllegalCall(value)
+    }
+}
+
+impl From<ExchangeError> for ShuftError {
+    fn from(value: ExchangeError) -> Self {
+        ShuftError::IllegalExchange(value)
+    }
+}
+
+impl From<ParseCardError> for ShuftError {
+    fn from(value: ParseCardError) -> Self {
+        ShuftError::Parse(value)
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use std::error::Error;
+
+    use crate::common::{cards::ItalianCard, hands::PlayError};
+
+    use super::ShuftError;
+
+    #[test]
+    fn errors_keep_their_source() {
+        let parse = "11H".parse::<ItalianCard>().unwrap_err();
+        let error = ShuftError::from(parse.clone());
+        assert_eq!(error, ShuftError::Parse(parse));
+        assert!(error.source().is_some());
+        assert_eq!(error.to_string(), "parse error: invalid rank: \"11\"");
+
+        let error = ShuftError::from(PlayError::Revoke);
+        assert!(matches!(error, ShuftError::IllegalPlay(PlayError::Revoke)));
+        assert!(ShuftError::IncompleteTrick.source().is_none());
+    }
+}
@@ -1,40 +1,471 @@
-use std::{fmt::Display, ops::Deref};
+use std::{error::Error, fmt::Display, ops::Deref, str::FromStr};
 
 use crate::common::{
-    cards::{Card, ItalianCard, ItalianRank, Suit},
-    hands::{Hand, OngoingTrick, Player, PlayerId, TrickTakingGame},
+    cards::{Card, CardSet, Deck, ItalianCard, ItalianRank, ParseCardError, Ranked, Suit, Suited},
+    dealer::{Deal, DealPattern, Dealer},
+    game::{Game, GameRules, Phase},
+    hands::{
+        follow_suit, highest_trump_or_led, Declarations, Hand, HandContext, OngoingTrick, Player,
+        PlayerId, TrickStrength, TrickTakingGame,
+    },
+    stock::Stock,
+    teams::{Scoreboard, Teams},
 };
+use crate::error::ShuftError;
 use num_rational::Rational32;
 use std::cmp::Ordering;
+use strum::IntoEnumIterator;
 
-#[derive(Clone, Debug, Default)]
-/// Contains the rules of the tressette game.
-pub struct TressetteRules {}
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+/// Contains the rules of the tressette game. The variant played can be
+/// configured: by default games are played to 31, ties are played out, the
+/// last trick is worth 1 point, fractions of points are lost, taking every
+/// point has no bonus and accusi aren't scored.
+///
+/// # Examples
+///
+/// ```
+/// use shuftlib::common::teams::Scoreboard;
+/// use shuftlib::tressette::{Cappotto, TiePolicy, TressetteRules};
+///
+/// let rules = TressetteRules::default()
+///     .with_target(21)
+///     .with_tie_policy(TiePolicy::Draw)
+///     .with_cappotto(Cappotto::Bonus(11));
+///
+/// assert!(rules.is_completed(&Scoreboard::from(vec![21, 12])));
+/// assert!(rules.is_completed(&Scoreboard::from(vec![23, 23])));
+/// assert_eq!(rules.winner(&Scoreboard::from(vec![23, 23])), None);
+/// ```
+pub struct TressetteRules {
+    target: i32,
+    tie_policy: TiePolicy,
+    last_trick_bonus: i32,
+    cappotto: Cappotto,
+    rounding: Rounding,
+    accusi: bool,
+}
+
+/// What happens when several teams reach the target score of a game of
+/// tressette with the same score.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum TiePolicy {
+    /// The game goes on until a single team has the highest score ("a
+    /// oltranza").
+    #[default]
+    PlayOn,
+    /// The game ends in a draw.
+    Draw,
+}
+
+/// How a hand where a team takes every card worth points, called cappotto
+/// (or stramazzo), is scored. The last trick doesn't matter: the team makes
+/// cappotto even if another team takes it.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Cappotto {
+    /// The hand is scored as usual.
+    #[default]
+    Ignored,
+    /// The team scores the given number of points on top of the ones of the
+    /// hand.
+    Bonus(i32),
+    /// The team wins the game: its score is raised to the target, or above
+    /// the score of every other team if that's higher.
+    WinsGame,
+}
+
+/// How the fractions of points taken in a hand are turned into points.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Rounding {
+    /// Fractions are lost: 2/3 of a point are worth nothing.
+    #[default]
+    Down,
+    /// Fractions are rounded to the nearest point: 2/3 of a point are worth
+    /// one point, so a hand can be worth more than 11 points.
+    Nearest,
+}
+
+impl Rounding {
+    /// Turns the fractions of points taken into points.
+    fn round(self, points: Rational32) -> i32 {
+        match self {
+            Rounding::Down => points.floor(),
+            Rounding::Nearest => points.round(),
+        }
+        .to_integer()
+    }
+}
+
+/// The points of the cards taken by each team in `hand`, including the
+/// fractions of points. The bonus for the last trick isn't included, see
+/// `add_last_trick_bonus`.
+fn points_taken<G>(hand: &Hand<G>, teams: &Teams) -> Vec<Rational32>
+where
+    G: TrickTakingGame<CardType = TressetteCard>,
+{
+    let mut points = vec![Rational32::new(0, 3); teams.count(hand.players())];
+    for trick in hand.tricks() {
+        points[teams.team(*trick.taker())] +=
+            trick.cards().iter().map(|c| c.value()).sum::<Rational32>();
+    }
+
+    points
+}
+
+/// Adds `bonus` points to the team taking the last trick of `hand`.
+fn add_last_trick_bonus<G>(points: &mut [Rational32], hand: &Hand<G>, teams: &Teams, bonus: i32)
+where
+    G: TrickTakingGame<CardType = TressetteCard>,
+{
+    if let Some(last) = hand.tricks().last() {
+        points[teams.team(*last.taker())] += bonus;
+    }
+}
+
+/// The only team that took points with its cards in `points`, if any. The
+/// bonus for the last trick doesn't count, so a team taking every card worth
+/// points makes cappotto even if it doesn't take the last trick.
+fn cappotto_team(points: &[Rational32]) -> Option<usize> {
+    let mut scoring = (0..points.len()).filter(|&team| points[team] > Rational32::from_integer(0));
+    match (scoring.next(), scoring.next()) {
+        (Some(team), None) => Some(team),
+        _ => None,
+    }
+}
+
+/// A combination of cards that a player of tressette can declare at their
+/// first turn, called accuso. Each card of the combination is worth one
+/// point.
+///
+/// # Examples
+///
+/// ```
+/// use shuftlib::common::cards::{CardSet, ItalianRank, Suit};
+/// use shuftlib::tressette::{Accuso, TressetteCard};
+///
+/// let hand: CardSet<TressetteCard> = ["1C", "2C", "3C", "4C", "1S", "1H"]
+///     .iter()
+///     .map(|c| c.parse().unwrap())
+///     .collect();
+///
+/// let accusi = Accuso::find(&hand);
+/// assert_eq!(accusi, [
+///     Accuso::Napoletana { suit: Suit::Clubs, length: 4 },
+///     Accuso::Set { rank: ItalianRank::Ace, missing: Some(Suit::Diamonds) },
+/// ]);
+/// assert_eq!(accusi[0].points(), 4);
+/// assert_eq!(accusi[1].points(), 3);
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Accuso {
+    /// The ace, the two and the three of a suit (napoletana), followed by
+    /// the next cards of the same suit, if held.
+    Napoletana {
+        /// The suit of the cards.
+        suit: Suit,
+        /// The number of cards, at least 3.
+        length: usize,
+    },
+    /// Three or four aces, twos or threes.
+    Set {
+        /// The rank of the cards.
+        rank: ItalianRank,
+        /// The suit of the card that isn't held, or None for four cards.
+        missing: Option<Suit>,
+    },
+}
+
+impl Accuso {
+    /// Finds every combination that can be declared with `hand`. The same
+    /// card can be part of a napoletana and of a set.
+    pub fn find(hand: &CardSet<TressetteCard>) -> Vec<Accuso> {
+        let mut accusi = Vec::new();
+        for suit in Suit::iter() {
+            let length = ItalianRank::iter()
+                .take_while(|&rank| hand.contains(TressetteCard::new(rank, suit)))
+                .count();
+            if length >= 3 {
+                accusi.push(Accuso::Napoletana { suit, length });
+            }
+        }
+
+        for rank in [ItalianRank::Ace, ItalianRank::Two, ItalianRank::Three] {
+            let missing: Vec<_> = Suit::iter()
+                .filter(|&suit| !hand.contains(TressetteCard::new(rank, suit)))
+                .collect();
+            match missing[..] {
+                [] => accusi.push(Accuso::Set {
+                    rank,
+                    missing: None,
+                }),
+                [suit] => accusi.push(Accuso::Set {
+                    rank,
+                    missing: Some(suit),
+                }),
+                _ => (),
+            }
+        }
+
+        accusi
+    }
+
+    /// The combination made of exactly `cards`, if any: this is how accusi
+    /// are recorded in a hand, see `Hand::accusi`.
+    pub fn from_cards(cards: &CardSet<TressetteCard>) -> Option<Accuso> {
+        Accuso::find(cards)
+            .into_iter()
+            .find(|accuso| accuso.cards() == *cards)
+    }
+
+    /// The cards of the combination.
+    pub fn cards(&self) -> CardSet<TressetteCard> {
+        match *self {
+            Accuso::Napoletana { suit, length } => ItalianRank::iter()
+                .take(length)
+                .map(|rank| TressetteCard::new(rank, suit))
+                .collect(),
+            Accuso::Set { rank, missing } => Suit::iter()
+                .filter(|&suit| Some(suit) != missing)
+                .map(|suit| TressetteCard::new(rank, suit))
+                .collect(),
+        }
+    }
+
+    /// The points scored by declaring the combination: 3 for a napoletana,
+    /// plus one for every other card, and 3 or 4 for a set.
+    pub fn points(&self) -> i32 {
+        match *self {
+            Accuso::Napoletana { length, .. } => length as i32,
+            Accuso::Set {
+                missing: Some(_), ..
+            } => 3,
+            Accuso::Set { missing: None, .. } => 4,
+        }
+    }
+}
+
+/// A signal that the leader of a trick can give to their partner together
+/// with the card they lead, in tressette in four. Signals are public: every
+/// player sees them, and they are kept with the hand, see
+/// `TressetteGame::signals`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Signal {
+    /// The partner should play their best card of the suit.
+    Busso,
+    /// The leader has more cards of the suit.
+    Striscio,
+    /// The card led is the last one of its suit held by the leader.
+    Volo,
+}
+
+impl Display for Signal {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Signal::Busso => write!(f, "busso"),
+            Signal::Striscio => write!(f, "striscio"),
+            Signal::Volo => write!(f, "volo"),
+        }
+    }
+}
+
+impl FromStr for Signal {
+    type Err = ParseMoveError;
+
+    /// Parses the name of a signal, ignoring case.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_lowercase().as_str() {
+            "busso" => Ok(Signal::Busso),
+            "striscio" => Ok(Signal::Striscio),
+            "volo" => Ok(Signal::Volo),
+            _ => Err(ParseMoveError::InvalidSignal(s.to_string())),
+        }
+    }
+}
+
+/// The reasons why a string isn't a valid `TressetteMove`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ParseMoveError {
+    /// The card of the move isn't valid.
+    Card(ParseCardError),
+    /// The signal of the move isn't valid.
+    InvalidSignal(String),
+}
+
+impl Display for ParseMoveError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ParseMoveError::Card(e) => write!(f, "{}", e),
+            ParseMoveError::InvalidSignal(s) => write!(f, "invalid signal: {:?}", s),
+        }
+    }
+}
+
+impl Error for ParseMoveError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            ParseMoveError::Card(e) => Some(e),
+            ParseMoveError::InvalidSignal(_) => None,
+        }
+    }
+}
+
+impl From<ParseCardError> for ParseMoveError {
+    fn from(value: ParseCardError) -> Self {
+        ParseMoveError::Card(value)
+    }
+}
+
+/// The reasons why the signal of a `TressetteMove` can't be given.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SignalError {
+    /// Only the leader of a trick can signal, with the card they lead.
+    NotLeader,
+    /// The signal can't be given with the card, see
+    /// `TressetteRules::can_signal`.
+    NotAllowed,
+}
+
+impl Display for SignalError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SignalError::NotLeader => write!(f, "only the leader of the trick can signal"),
+            SignalError::NotAllowed => write!(f, "the signal can't be given with this card"),
+        }
+    }
+}
+
+impl Error for SignalError {}
+
+/// A move of tressette: the card played and, for the leader of a trick, the
+/// signal given with it. Its notation is the one of the card, followed by
+/// the signal if any, like "3C" or "3C busso".
+///
+/// # Examples
+///
+/// ```
+/// use shuftlib::common::cards::{ItalianRank, Suit};
+/// use shuftlib::tressette::{Signal, TressetteCard, TressetteMove};
+///
+/// let card = TressetteCard::new(ItalianRank::Three, Suit::Clubs);
+/// let mv = TressetteMove::new(card, Some(Signal::Busso));
+/// assert_eq!(mv.to_string(), "3C busso");
+/// assert_eq!("3C busso".parse::<TressetteMove>(), Ok(mv));
+/// assert_eq!("3C".parse::<TressetteMove>(), Ok(TressetteMove::from(card)));
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct TressetteMove {
+    card: TressetteCard,
+    signal: Option<Signal>,
+}
+
+impl TressetteMove {
+    /// Creates a move playing `card`, with an optional signal.
+    pub fn new(card: TressetteCard, signal: Option<Signal>) -> Self {
+        Self { card, signal }
+    }
+
+    /// The card played.
+    pub fn card(&self) -> TressetteCard {
+        self.card
+    }
+
+    /// The signal given with the card, if any.
+    pub fn signal(&self) -> Option<Signal> {
+        self.signal
+    }
+}
+
+impl From<TressetteCard> for TressetteMove {
+    fn from(card: TressetteCard) -> Self {
+        Self::new(card, None)
+    }
+}
+
+impl Display for TressetteMove {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self.signal {
+            Some(signal) => write!(f, "{} {}", self.card, signal),
+            None => write!(f, "{}", self.card),
+        }
+    }
+}
+
+impl FromStr for TressetteMove {
+    type Err = ParseMoveError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut parts = s.split_whitespace();
+        let card = parts.next().ok_or(ParseCardError::Empty)?.parse()?;
+        let signal = match (parts.next(), parts.next()) {
+            (None, _) => None,
+            (Some(signal), None) => Some(signal.parse()?),
+            (Some(_), Some(_)) => return Err(ParseMoveError::InvalidSignal(s.to_string())),
+        };
+
+        Ok(Self::new(card, signal))
+    }
+}
+
+#[cfg(feature = "serde")]
+crate::common::cards::serde_via_str!(Signal, TressetteMove);
+
+impl Default for TressetteRules {
+    fn default() -> Self {
+        Self {
+            target: SCORE_TO_WIN,
+            tie_policy: TiePolicy::PlayOn,
+            last_trick_bonus: 1,
+            cappotto: Cappotto::Ignored,
+            rounding: Rounding::Down,
+            accusi: false,
+        }
+    }
+}
 
 impl TrickTakingGame for TressetteRules {
     type CardType = TressetteCard;
+    type Contract = ();
+    type Bid = ();
 
     const PLAYERS: usize = 4;
     const TRICKS: usize = 10;
 
+    /// Tressette can also be played by 2 (tressette a due) or 3 players
+    /// (tressette in tre).
+    fn supports(players: usize) -> bool {
+        (2..=4).contains(&players)
+    }
+
+    /// In four, players sitting opposite each other play together. With
+    /// fewer players, every player plays for themselves.
+    fn teams(players: usize) -> Teams {
+        if players == 4 {
+            Teams::Partnerships(2)
+        } else {
+            Teams::Individual
+        }
+    }
+
     /// Contains the logic to determine who won the trick in a standard
     /// tressette game: The winner of the trick is always the player who played
     /// the highest card with the same `Suit` of the first `TressetteCard`
-    /// played that trick. See the implementation of `Ord` and `PartialOrd` for
-    /// `TressetteCard` for more info. The implementation of this trait is meant
-    /// to only be used internally by `OngoingTrick`, however it's possible to
-    /// call it elsewhere if needed. It also assumes the slice `cards` is valid
-    /// for the tressette game, so it assumes there are no duplicates. It's a
-    /// responsability of the caller to make sure that's the case.
-    ///
-    /// # Panics
-    ///
-    /// It can only panic in case of a bug in this crate.
+    /// played that trick, since there are no trumps. See the implementation
+    /// of `TrickStrength` for `TressetteRules` for more info. The
+    /// implementation of this trait is meant to only be used internally by
+    /// `OngoingTrick`, however it's possible to call it elsewhere if needed.
+    /// It also assumes the slice `cards` is valid for the tressette game, so
+    /// it assumes there are no duplicates. It's a responsability of the caller
+    /// to make sure that's the case.
     ///
     /// # Examples
     ///
     /// ```
-    /// use shuftlib::common::{hands::{TrickTakingGame, PlayerId}, cards::{ItalianRank, Suit}};
+    /// use shuftlib::common::{hands::{HandContext, TrickTakingGame, PlayerId}, cards::{ItalianRank, Suit}};
     /// use shuftlib::tressette::{TressetteRules, TressetteCard};
     ///
     /// let cards = [
@@ -44,40 +475,214 @@ impl TrickTakingGame for TressetteRules {
     ///   TressetteCard::new(ItalianRank::Four, Suit::Hearts),
     /// ];
     ///
-    /// let taker = TressetteRules::determine_taker(&cards, PlayerId::new(2).unwrap());
+    /// let context = HandContext::new();
+    /// let taker = TressetteRules::determine_taker(&cards, PlayerId::new(2).unwrap(), &context);
     /// assert_eq!(taker, PlayerId::new(2).unwrap());
     /// ```
-    #[allow(clippy::expect_used)]
     fn determine_taker(
-        cards: &[TressetteCard; Self::PLAYERS],
-        first_to_play: PlayerId<{ Self::PLAYERS }>,
-    ) -> PlayerId<{ Self::PLAYERS }> {
-        let leading_suit = cards[*first_to_play].suit();
-        let (taker, _) = cards
-            .iter()
-            .enumerate()
-            .filter(|(_, &c)| c.suit() == leading_suit)
-            .max_by_key(|(_, &c)| c)
-            .expect("Max by key returned None. This shouldn't have happened, since it's being called on a non empty slice.");
+        cards: &[TressetteCard],
+        first_to_play: PlayerId<Self>,
+        context: &HandContext<Self>,
+    ) -> PlayerId<Self> {
+        highest_trump_or_led(cards, first_to_play, context)
+    }
+}
 
-        PlayerId::new(taker).expect("Initialization of a new PlayerId failed. This shouldn't have happened, since the input usize was computed starting from a fixed length slice.")
+impl Declarations for TressetteRules {
+    /// The cards must be the ones of an accuso found in `hand`, see
+    /// `Accuso::find`.
+    fn can_declare(hand: &CardSet<TressetteCard>, cards: &CardSet<TressetteCard>) -> bool {
+        Accuso::from_cards(cards).is_some_and(|accuso| Accuso::find(hand).contains(&accuso))
     }
 }
 
-/// The score a team has to reach to win a game of tressette.
-pub const SCORE_TO_WIN: u8 = 31;
+impl TrickStrength for TressetteRules {
+    /// Only cards of the leading suit can take the trick. Among them, the
+    /// ranks go from the 3 (the highest) to the 2, ace, king, knight, jack,
+    /// 7, 6, 5 and 4 (the lowest).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::cmp::Ordering;
+    /// use shuftlib::common::{hands::{HandContext, TrickStrength}, cards::{ItalianRank, Suit}};
+    /// use shuftlib::tressette::{TressetteRules, TressetteCard};
+    ///
+    /// let led = TressetteCard::new(ItalianRank::Four, Suit::Hearts);
+    /// let three = TressetteCard::new(ItalianRank::Three, Suit::Hearts);
+    /// let ace = TressetteCard::new(ItalianRank::Ace, Suit::Hearts);
+    /// let off_suit = TressetteCard::new(ItalianRank::Three, Suit::Clubs);
+    ///
+    /// let context = HandContext::new();
+    ///
+    /// assert_eq!(TressetteRules::compare(&led, &three, &ace, &context), Ordering::Greater);
+    /// assert_eq!(TressetteRules::compare(&led, &off_suit, &led, &context), Ordering::Less);
+    /// ```
+    fn compare(
+        led: &TressetteCard,
+        a: &TressetteCard,
+        b: &TressetteCard,
+        _context: &HandContext<Self>,
+    ) -> Ordering {
+        let follows = |c: &TressetteCard| c.suit() == led.suit();
+        match (follows(a), follows(b)) {
+            (true, true) => rank_strength(a.rank()).cmp(&rank_strength(b.rank())),
+            (true, false) => Ordering::Greater,
+            (false, true) => Ordering::Less,
+            (false, false) => Ordering::Equal,
+        }
+    }
+}
+
+/// The strength of a rank in a trick of tressette, from 0 (the 4) to 9 (the 3).
+fn rank_strength(rank: ItalianRank) -> u8 {
+    match rank {
+        ItalianRank::Four => 0,
+        ItalianRank::Five => 1,
+        ItalianRank::Six => 2,
+        ItalianRank::Seven => 3,
+        ItalianRank::Jack => 4,
+        ItalianRank::Knight => 5,
+        ItalianRank::King => 6,
+        ItalianRank::Ace => 7,
+        ItalianRank::Two => 8,
+        ItalianRank::Three => 9,
+    }
+}
+
+impl GameRules for TressetteRules {
+    type Score = Scoreboard;
+
+    fn deck(&self) -> Deck<TressetteCard> {
+        Deck::tressette()
+    }
+
+    /// Cards are dealt in packets of 5 when possible, one at a time
+    /// otherwise. Every player receives the same number of cards and the
+    /// remaining ones are left out of the hand. In two, each player receives
+    /// 10 cards and the other ones make the stock.
+    fn dealer(&self, players: usize) -> Dealer {
+        let each = ItalianCard::DECK_SIZE / players.max(1);
+        if players == 2 {
+            Dealer::new(DealPattern::packets(5, 2))
+        } else if each.is_multiple_of(5) {
+            Dealer::new(DealPattern::packets(5, each / 5))
+        } else {
+            Dealer::new(DealPattern::round_robin(each))
+        }
+    }
+
+    /// In two, players draw from the stock after every trick, showing the
+    /// drawn card to the opponent.
+    fn stock(&self, deal: &Deal<TressetteCard>, players: usize) -> Option<Stock<TressetteCard>> {
+        (players == 2).then(|| Stock::new(deal.stock().clone()))
+    }
+
+    fn score_hand(&self, hand: &Hand<Self>, score: &mut Scoreboard) {
+        self.compute_score(hand, score);
+    }
+
+    fn is_completed(&self, score: &Scoreboard) -> bool {
+        TressetteRules::is_completed(self, score)
+    }
+}
+
+/// The score a team has to reach to win a game of tressette, unless the rules
+/// set a different target.
+pub const SCORE_TO_WIN: i32 = 31;
 
 impl TressetteRules {
-    /// Determines if a team won the game. A team wins the game when its score is
-    /// greater than 31 and has a higher score than the other team.
-    pub fn is_completed(score: (u8, u8)) -> bool {
-        (score.0 >= SCORE_TO_WIN && score.0 > score.1)
-            || (score.1 >= SCORE_TO_WIN && score.1 > score.0)
+    /// Sets the score a team has to reach to win the game, usually 21, 31 or
+    /// 41.
+    pub fn with_target(mut self, target: i32) -> Self {
+        self.target = target;
+        self
+    }
+
+    /// Sets what happens when teams reach the target with the same score.
+    pub fn with_tie_policy(mut self, tie_policy: TiePolicy) -> Self {
+        self.tie_policy = tie_policy;
+        self
+    }
+
+    /// Sets the points scored by the team taking the last trick of a hand.
+    pub fn with_last_trick_bonus(mut self, points: i32) -> Self {
+        self.last_trick_bonus = points;
+        self
+    }
+
+    /// Sets how a team taking every point of a hand is scored.
+    pub fn with_cappotto(mut self, cappotto: Cappotto) -> Self {
+        self.cappotto = cappotto;
+        self
+    }
+
+    /// Sets how fractions of points are turned into points.
+    pub fn with_rounding(mut self, rounding: Rounding) -> Self {
+        self.rounding = rounding;
+        self
+    }
+
+    /// Sets whether the accusi declared by the players are scored.
+    pub fn with_accusi(mut self, accusi: bool) -> Self {
+        self.accusi = accusi;
+        self
+    }
+
+    /// The score a team has to reach to win the game.
+    pub fn target(&self) -> i32 {
+        self.target
+    }
+
+    /// What happens when teams reach the target with the same score.
+    pub fn tie_policy(&self) -> TiePolicy {
+        self.tie_policy
+    }
+
+    /// The points scored by the team taking the last trick of a hand.
+    pub fn last_trick_bonus(&self) -> i32 {
+        self.last_trick_bonus
+    }
+
+    /// How a team taking every point of a hand is scored.
+    pub fn cappotto(&self) -> Cappotto {
+        self.cappotto
+    }
+
+    /// How fractions of points are turned into points.
+    pub fn rounding(&self) -> Rounding {
+        self.rounding
+    }
+
+    /// Whether the accusi declared by the players are scored.
+    pub fn accusi(&self) -> bool {
+        self.accusi
+    }
+
+    /// Determines if the game is over. It is when a team has reached the
+    /// target and has a higher score than every other team, or when several
+    /// teams reached it with the same score and ties end in a draw.
+    pub fn is_completed(&self, scoreboard: &Scoreboard) -> bool {
+        match scoreboard.leader() {
+            Some(team) => scoreboard.score(team) >= self.target,
+            None => {
+                self.tie_policy == TiePolicy::Draw
+                    && scoreboard.scores().iter().any(|&s| s >= self.target)
+            }
+        }
+    }
+
+    /// The team that won the game, or None if the game isn't over or ended in
+    /// a draw.
+    pub fn winner(&self, scoreboard: &Scoreboard) -> Option<usize> {
+        scoreboard
+            .leader()
+            .filter(|&team| scoreboard.score(team) >= self.target)
     }
 
-    /// Returns a view of the playable cards held by a player, based on the suit
-    /// of a card that has been played before and by the rules of tressette. If
-    /// the player is the first to play, the leading suit can be None.
+    /// Returns the playable cards held by a player, based on the suit of a card
+    /// that has been played before and by the rules of tressette. If the player
+    /// is the first to play, the leading suit can be None.
     ///
     /// # Examples
     ///
@@ -97,101 +702,576 @@ impl TressetteRules {
     pub fn playable(
         player: &Player<TressetteRules>,
         leading_suit: Option<Suit>,
-    ) -> Vec<TressetteCard> {
-        if let Some(leading_suit) = leading_suit {
-            if player.hand().iter().any(|c| c.suit() == leading_suit) {
-                return player
-                    .hand()
-                    .iter()
-                    .filter(|c| c.suit() == leading_suit)
-                    .cloned()
-                    .collect();
-            }
-        }
-
-        player.hand().into()
+    ) -> CardSet<TressetteCard> {
+        follow_suit(player.hand(), leading_suit)
     }
 
-    /// Plays the specified card for the player
+    /// Plays the specified card for the player, checking that the move is
+    /// legal.
+    ///
+    /// # Errors
+    ///
+    /// Fails if the card can't be played, see `OngoingTrick::play`.
     pub fn play(
         player: &mut Player<TressetteRules>,
         card: TressetteCard,
         ongoing_trick: &mut OngoingTrick<TressetteRules>,
-    ) {
-        player.remove(card);
-        ongoing_trick.play(card);
+    ) -> Result<(), ShuftError> {
+        ongoing_trick.play(player, card)
     }
 
-    /// Computes the score for a hand of the tressette game.
-    /// Score is always a maximum of 11 points.
-    pub fn compute_score(hand: &Hand<Self>, score: &mut (u8, u8)) {
-        let mut tmp_score = (Rational32::new(0, 3), Rational32::new(0, 3));
+    /// Whether the leader of a trick holding `hand` can give `signal` while
+    /// leading `card`. Volo can only be given with the last card of its suit
+    /// held, striscio only when more cards of the suit are held. Busso can
+    /// always be given.
+    pub fn can_signal(hand: &CardSet<TressetteCard>, card: TressetteCard, signal: Signal) -> bool {
+        let others = hand
+            .iter()
+            .filter(|c| *c != card && c.suit() == card.suit())
+            .count();
+        match signal {
+            Signal::Busso => true,
+            Signal::Striscio => others > 0,
+            Signal::Volo => others == 0,
+        }
+    }
 
-        let mut taker = 0;
-        for trick in hand.tricks() {
-            if *trick.taker() == 0 || *trick.taker() == 2 {
-                tmp_score.0 += trick.cards().iter().map(|c| c.value()).sum::<Rational32>();
-            } else {
-                tmp_score.1 += trick.cards().iter().map(|c| c.value()).sum::<Rational32>();
+    /// Plays the card of `mv` for the player, checking that the move is
+    /// legal: a signal can only be given by the leader of the trick, with the
+    /// card they lead. The signal isn't kept in the trick, see
+    /// `TressetteGame` for a game that keeps the signals.
+    ///
+    /// # Errors
+    ///
+    /// Fails with `ShuftError::IllegalSignal` if the player can't give the
+    /// signal, or if the card can't be played, see `OngoingTrick::play`. In
+    /// both cases, neither the trick nor the player are modified.
+    pub fn play_move(
+        player: &mut Player<TressetteRules>,
+        mv: TressetteMove,
+        ongoing_trick: &mut OngoingTrick<TressetteRules>,
+    ) -> Result<(), ShuftError> {
+        check_signal(player, ongoing_trick, mv)?;
+        ongoing_trick.play(player, mv.card())
+    }
+
+    /// Computes the score for a hand of the tressette game and adds it to the
+    /// scoreboard, whose teams are the ones of `TrickTakingGame::teams`. The
+    /// fractions of points taken by each team are rounded following the
+    /// rules, and the team taking the last trick gets the last trick bonus.
+    /// When they are enabled, the team of each player who declared accusi
+    /// scores their points too. With the default rules, a hand is worth at
+    /// most 11 points.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use shuftlib::common::{cards::Deck, hands::{OngoingHand, Player, PlayerId}, teams::Scoreboard};
+    /// use shuftlib::tressette::{Cappotto, TressetteRules};
+    ///
+    /// // Every player plays the cards of a different suit: player 0 always
+    /// // leads and takes every trick.
+    /// let mut players: Vec<Player<TressetteRules>> =
+    ///     (0..4).map(|i| Player::new(PlayerId::new(i).unwrap())).collect();
+    /// let deck = Deck::tressette();
+    /// let mut hand = OngoingHand::new(PlayerId::new(0).unwrap());
+    /// for trick in 0..10 {
+    ///     for (suit, player) in players.iter_mut().enumerate() {
+    ///         let card = deck[suit * 10 + trick];
+    ///         player.give(card);
+    ///         hand.play(player, card).unwrap();
+    ///     }
+    /// }
+    /// let hand = hand.finish().unwrap();
+    ///
+    /// let mut scoreboard = Scoreboard::default();
+    /// TressetteRules::default().compute_score(&hand, &mut scoreboard);
+    /// assert_eq!(scoreboard.scores(), [11, 0]);
+    ///
+    /// let mut scoreboard = Scoreboard::default();
+    /// let rules = TressetteRules::default().with_cappotto(Cappotto::Bonus(10));
+    /// rules.compute_score(&hand, &mut scoreboard);
+    /// assert_eq!(scoreboard.scores(), [21, 0]);
+    /// ```
+    pub fn compute_score(&self, hand: &Hand<Self>, scoreboard: &mut Scoreboard) {
+        let teams = Self::teams(hand.players());
+        let count = teams.count(hand.players());
+        let mut points = points_taken(hand, &teams);
+        let cappotto = cappotto_team(&points);
+        add_last_trick_bonus(&mut points, hand, &teams, self.last_trick_bonus);
+
+        for (team, points) in points.iter().enumerate() {
+            scoreboard.add(team, self.rounding.round(*points));
+        }
+
+        if self.accusi {
+            for (player, accuso) in hand.accusi() {
+                scoreboard.add_for(&teams, *player, accuso.points());
             }
+        }
 
-            taker = *trick.taker();
+        if let Some(team) = cappotto {
+            match self.cappotto {
+                Cappotto::Ignored => (),
+                Cappotto::Bonus(bonus) => scoreboard.add(team, bonus),
+                Cappotto::WinsGame => {
+                    let others = (0..count)
+                        .filter(|&other| other != team)
+                        .map(|other| scoreboard.score(other) + 1);
+                    let needed = others.fold(self.target, i32::max);
+                    scoreboard.add(team, (needed - scoreboard.score(team)).max(0));
+                }
+            }
         }
+    }
+}
+
+/// Contains the rules of ciapano (also known as rovescino), the reverse form
+/// of tressette: cards are played and take tricks like in tressette, but
+/// every player plays for themselves and tries to take as few points as
+/// possible. A player taking every point of a hand makes cappotto: they
+/// score nothing, and every other player scores the points of the hand
+/// instead. The game is over when a player reaches the limit, and it's won
+/// by the player with the lowest score. By default the limit is 31 and
+/// fractions of points are lost.
+///
+/// # Examples
+///
+/// ```
+/// use shuftlib::common::teams::Scoreboard;
+/// use shuftlib::tressette::CiapanoRules;
+///
+/// let rules = CiapanoRules::default().with_limit(21);
+/// let scoreboard = Scoreboard::from(vec![21, 4, 12, 7]);
+///
+/// assert!(rules.is_completed(&scoreboard));
+/// assert_eq!(rules.winner(&scoreboard), Some(1));
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct CiapanoRules {
+    limit: i32,
+    rounding: Rounding,
+}
 
-        score.0 += tmp_score.0.to_integer() as u8;
-        score.1 += tmp_score.1.to_integer() as u8;
+/// The score that makes a player lose a game of ciapano, unless the rules
+/// set a different limit.
+pub const SCORE_TO_LOSE: i32 = 31;
 
-        if taker == 0 || taker == 2 {
-            score.0 += 1;
-        } else {
-            score.1 += 1;
+impl Default for CiapanoRules {
+    fn default() -> Self {
+        Self {
+            limit: SCORE_TO_LOSE,
+            rounding: Rounding::default(),
         }
     }
 }
 
-#[derive(PartialEq, Eq, Debug, Clone, Copy, Default, Hash)]
-/// Representation of a card used in variations of the Tressette game. It's just
-/// a new type over `ItalianCard`.
-pub struct TressetteCard {
-    card: ItalianCard,
+impl TrickTakingGame for CiapanoRules {
+    type CardType = TressetteCard;
+    type Contract = ();
+    type Bid = ();
+
+    const PLAYERS: usize = 4;
+    const TRICKS: usize = 10;
+
+    /// Ciapano is played by 3 or 4 players.
+    fn supports(players: usize) -> bool {
+        (3..=4).contains(&players)
+    }
+
+    /// Every player plays for themselves, in four too.
+    fn teams(_players: usize) -> Teams {
+        Teams::Individual
+    }
+
+    /// The trick is taken like in tressette, see `TressetteRules`.
+    fn determine_taker(
+        cards: &[TressetteCard],
+        first_to_play: PlayerId<Self>,
+        context: &HandContext<Self>,
+    ) -> PlayerId<Self> {
+        highest_trump_or_led(cards, first_to_play, context)
+    }
 }
 
-impl PartialOrd for TressetteCard {
-    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
-        Some(self.cmp(other))
+impl TrickStrength for CiapanoRules {
+    /// Cards are compared like in tressette, see `TressetteRules`.
+    fn compare(
+        led: &TressetteCard,
+        a: &TressetteCard,
+        b: &TressetteCard,
+        _context: &HandContext<Self>,
+    ) -> Ordering {
+        TressetteRules::compare(led, a, b, &HandContext::new())
     }
 }
 
-impl Ord for TressetteCard {
-    #[allow(clippy::expect_used)]
-    fn cmp(&self, other: &Self) -> Ordering {
-        let rank_order = [
-            ItalianRank::Four,
-            ItalianRank::Five,
-            ItalianRank::Six,
-            ItalianRank::Seven,
-            ItalianRank::Jack,
-            ItalianRank::Knight,
-            ItalianRank::King,
-            ItalianRank::Ace,
-            ItalianRank::Two,
-            ItalianRank::Three,
-        ];
+impl GameRules for CiapanoRules {
+    type Score = Scoreboard;
+
+    fn deck(&self) -> Deck<TressetteCard> {
+        Deck::tressette()
+    }
+
+    /// Cards are dealt like in tressette: in packets of 5 in four, one at a
+    /// time in three, with the remaining card left out of the hand.
+    fn dealer(&self, players: usize) -> Dealer {
+        TressetteRules::default().dealer(players)
+    }
+
+    fn score_hand(&self, hand: &Hand<Self>, score: &mut Scoreboard) {
+        self.compute_score(hand, score);
+    }
+
+    fn is_completed(&self, score: &Scoreboard) -> bool {
+        CiapanoRules::is_completed(self, score)
+    }
+}
+
+impl CiapanoRules {
+    /// Sets the score that makes a player lose the game.
+    pub fn with_limit(mut self, limit: i32) -> Self {
+        self.limit = limit;
+        self
+    }
+
+    /// Sets how fractions of points are turned into points.
+    pub fn with_rounding(mut self, rounding: Rounding) -> Self {
+        self.rounding = rounding;
+        self
+    }
+
+    /// The score that makes a player lose the game.
+    pub fn limit(&self) -> i32 {
+        self.limit
+    }
+
+    /// How fractions of points are turned into points.
+    pub fn rounding(&self) -> Rounding {
+        self.rounding
+    }
+
+    /// Determines if the game is over. It is when a player has reached the
+    /// limit and a single player has the lowest score; ties for the lowest
+    /// score are played out.
+    pub fn is_completed(&self, scoreboard: &Scoreboard) -> bool {
+        self.winner(scoreboard).is_some()
+    }
+
+    /// The player who won the game, the one with the lowest score once
+    /// another player has reached the limit, or None if the game isn't over.
+    pub fn winner(&self, scoreboard: &Scoreboard) -> Option<usize> {
+        let scores = scoreboard.scores();
+        if !scores.iter().any(|&s| s >= self.limit) {
+            return None;
+        }
+        let (winner, &lowest) = scores.iter().enumerate().min_by_key(|(_, &s)| s)?;
+        let ties = scores.iter().filter(|&&s| s == lowest).count();
+
+        (ties == 1).then_some(winner)
+    }
+
+    /// Returns the playable cards held by a player: like in tressette, they
+    /// must follow the leading suit if they can, see
+    /// `TressetteRules::playable`.
+    pub fn playable(
+        player: &Player<CiapanoRules>,
+        leading_suit: Option<Suit>,
+    ) -> CardSet<TressetteCard> {
+        follow_suit(player.hand(), leading_suit)
+    }
+
+    /// Computes the score for a hand of ciapano and adds it to the
+    /// scoreboard, where every player has their own score. Players score the
+    /// points they took, counted like in tressette with 1 point for the last
+    /// trick, and a player taking every card worth points makes cappotto.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use shuftlib::common::{cards::Deck, hands::{OngoingHand, Player, PlayerId}, teams::Scoreboard};
+    /// use shuftlib::tressette::CiapanoRules;
+    ///
+    /// // Every player plays the cards of a different suit: player 0 always
+    /// // leads and takes every trick.
+    /// let mut players: Vec<Player<CiapanoRules>> =
+    ///     (0..4).map(|i| Player::new(PlayerId::new(i).unwrap())).collect();
+    /// let deck = Deck::tressette();
+    /// let mut hand = OngoingHand::new(PlayerId::new(0).unwrap());
+    /// for trick in 0..10 {
+    ///     for (suit, player) in players.iter_mut().enumerate() {
+    ///         let card = deck[suit * 10 + trick];
+    ///         player.give(card);
+    ///         hand.play(player, card).unwrap();
+    ///     }
+    /// }
+    /// let hand = hand.finish().unwrap();
+    ///
+    /// let mut scoreboard = Scoreboard::default();
+    /// CiapanoRules::default().compute_score(&hand, &mut scoreboard);
+    /// assert_eq!(scoreboard.scores(), [0, 11, 11, 11]);
+    /// ```
+    pub fn compute_score(&self, hand: &Hand<Self>, scoreboard: &mut Scoreboard) {
+        let teams = Self::teams(hand.players());
+        let mut points = points_taken(hand, &teams);
+        let cappotto = cappotto_team(&points);
+        add_last_trick_bonus(&mut points, hand, &teams, 1);
 
-        let self_rank_index = rank_order.iter().position(|&r| self.card.rank() == r).expect("The rank of self wasn't found inside the Ord implementation for TressetteCard. This shouldn't have happened, please file a bug report.");
-        let other_rank_index = rank_order.iter().position(|&r| other.card.rank() == r).expect("The rank of other wasn't found inside the Ord implementation for TressetteCard. This shouldn't have happened, please file a bug report.");
+        if let Some(cappotto) = cappotto {
+            let taken = self.rounding.round(points.iter().sum());
+            for player in (0..points.len()).filter(|&player| player != cappotto) {
+                scoreboard.add(player, taken);
+            }
+            scoreboard.add(cappotto, 0);
+            return;
+        }
 
-        self_rank_index.cmp(&other_rank_index)
+        for (player, points) in points.into_iter().enumerate() {
+            scoreboard.add(player, self.rounding.round(points));
+        }
     }
 }
 
+#[derive(PartialEq, Eq, PartialOrd, Ord, Debug, Clone, Copy, Default, Hash)]
+/// Representation of a card used in variations of the Tressette game. It's just
+/// a new type over `ItalianCard`, and it's ordered in the same way. The
+/// strength of the cards in a trick is given by `TrickStrength`.
+pub struct TressetteCard {
+    card: ItalianCard,
+}
+
 impl Display for TressetteCard {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         write!(f, "{}", self.card)
     }
 }
 
-impl Card for TressetteCard {}
+impl FromStr for TressetteCard {
+    type Err = ParseCardError;
+
+    /// Parses a `TressetteCard` with the same notation accepted by
+    /// `ItalianCard`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use shuftlib::{tressette::TressetteCard, common::cards::{Suit, ItalianRank}};
+    ///
+    /// let card = TressetteCard::new(ItalianRank::Three, Suit::Clubs);
+    /// assert_eq!("3C".parse::<TressetteCard>(), Ok(card));
+    /// assert_eq!(card.to_string().parse::<TressetteCard>(), Ok(card));
+    /// ```
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        s.parse::<ItalianCard>().map(TressetteCard::from)
+    }
+}
+
+impl Card for TressetteCard {
+    const DECK_SIZE: usize = ItalianCard::DECK_SIZE;
+
+    fn index(&self) -> usize {
+        self.card.index()
+    }
+
+    fn from_index(index: usize) -> Option<Self> {
+        ItalianCard::from_index(index).map(TressetteCard::from)
+    }
+}
+
+impl Suited for TressetteCard {
+    fn suit(&self) -> Suit {
+        self.card.suit()
+    }
+}
+
+impl Ranked for TressetteCard {
+    type Rank = ItalianRank;
+
+    fn rank(&self) -> ItalianRank {
+        self.card.rank()
+    }
+}
+
+#[cfg(feature = "serde")]
+crate::common::cards::serde_via_str!(TressetteCard);
+
+impl Deck<TressetteCard> {
+    /// Returns a new, unshuffled deck of `TressetteCard`, in the same order as
+    /// `Deck::italian`.
+    pub fn tressette() -> Deck<TressetteCard> {
+        Deck::from_vec(
+            Deck::italian()
+                .iter()
+                .map(|&c| TressetteCard::from(c))
+                .collect(),
+        )
+    }
+}
+
+impl Hand<TressetteRules> {
+    /// The accusi declared by the players, in the order they have been
+    /// declared, see `Hand::declarations`.
+    pub fn accusi(&self) -> Vec<(PlayerId<TressetteRules>, Accuso)> {
+        self.declarations()
+            .iter()
+            .filter_map(|(player, cards)| Some((*player, Accuso::from_cards(cards)?)))
+            .collect()
+    }
+}
+
+/// Checks that `player` can give the signal of `mv`, if any, in `trick`.
+fn check_signal(
+    player: &Player<TressetteRules>,
+    trick: &OngoingTrick<TressetteRules>,
+    mv: TressetteMove,
+) -> Result<(), SignalError> {
+    let Some(signal) = mv.signal() else {
+        return Ok(());
+    };
+    if trick.led().is_some() || player.id() != trick.first_to_play() {
+        return Err(SignalError::NotLeader);
+    }
+    if !TressetteRules::can_signal(player.hand(), mv.card(), signal) {
+        return Err(SignalError::NotAllowed);
+    }
+
+    Ok(())
+}
+
+/// A complete game of tressette, played one `TressetteMove` at a time. It
+/// plays a `Game` of `TressetteRules`, which it derefs to, and keeps the
+/// signals given by the leaders of the tricks, which are public: every
+/// player can see them. With the `serde` feature, the game is saved with its
+/// signals, so that the hands are never separated from them.
+///
+/// # Examples
+///
+/// ```
+/// use shuftlib::common::game::Phase;
+/// use shuftlib::tressette::{Signal, TressetteGame, TressetteMove, TressetteRules};
+///
+/// let mut game = TressetteGame::new(TressetteRules::default(), 42).unwrap();
+/// let card = game.legal_moves().iter().next().unwrap();
+/// game.apply_move(TressetteMove::new(card, Some(Signal::Busso))).unwrap();
+/// assert_eq!(game.current_signal(), Some(Signal::Busso));
+///
+/// // Only the leader can signal.
+/// let card = game.legal_moves().iter().next().unwrap();
+/// assert!(game.apply_move(TressetteMove::new(card, Some(Signal::Busso))).is_err());
+///
+/// while game.phase() != Phase::Completed {
+///     let card = game.legal_moves().iter().next().unwrap();
+///     game.apply_move(card.into()).unwrap();
+/// }
+/// assert_eq!(game.signals()[0][0], Some(Signal::Busso));
+/// ```
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct TressetteGame {
+    game: Game<TressetteRules>,
+    /// The signal given with the first card of every trick led so far, for
+    /// each hand.
+    signals: Vec<Vec<Option<Signal>>>,
+}
+
+impl TressetteGame {
+    /// Creates a game with the default number of players, see `Game::new`.
+    ///
+    /// # Errors
+    ///
+    /// Fails if the first hand can't be dealt.
+    pub fn new(rules: TressetteRules, seed: u64) -> Result<Self, ShuftError> {
+        Game::new(rules, seed).map(Self::start)
+    }
+
+    /// Same as `new`, but for the given number of players, see
+    /// `Game::with_players`.
+    ///
+    /// # Errors
+    ///
+    /// Fails if the game can't be played by that many players, or if the
+    /// first hand can't be dealt.
+    pub fn with_players(
+        rules: TressetteRules,
+        players: usize,
+        seed: u64,
+    ) -> Result<Self, ShuftError> {
+        Game::with_players(rules, players, seed).map(Self::start)
+    }
+
+    /// Keeps the signals of `game`, which has just been created.
+    fn start(game: Game<TressetteRules>) -> Self {
+        Self {
+            game,
+            signals: vec![Vec::new()],
+        }
+    }
+
+    /// Plays `mv` for the player whose turn it is, see `Game::apply`. Its
+    /// signal, if any, is kept with the trick it leads.
+    ///
+    /// # Errors
+    ///
+    /// Fails with `ShuftError::IllegalSignal` if the player isn't leading the
+    /// trick or can't give the signal with the card, or like `Game::apply`.
+    /// In every case the game isn't modified.
+    pub fn apply_move(&mut self, mv: TressetteMove) -> Result<(), ShuftError> {
+        if let Some(trick) = self.game.current_trick() {
+            check_signal(&self.game.players()[*trick.next_to_play()], trick, mv)?;
+        }
+
+        let leads = self
+            .game
+            .current_trick()
+            .is_some_and(|trick| trick.led().is_none());
+        let hand = self.game.hands().len();
+        self.game.apply(mv.card())?;
+        if leads {
+            self.signals[hand].push(mv.signal());
+        }
+        if self.game.phase() != Phase::Completed {
+            self.signals
+                .resize_with(self.game.hands().len() + 1, Vec::new);
+        }
+
+        Ok(())
+    }
+
+    /// Declares `accuso` for the player whose turn it is, which must be their
+    /// first turn of the hand, see `Game::declare`.
+    ///
+    /// # Errors
+    ///
+    /// Fails if the player can't declare the accuso.
+    pub fn declare(&mut self, accuso: Accuso) -> Result<(), ShuftError> {
+        self.game.declare(accuso.cards())
+    }
+
+    /// The signals given by the leaders, for every hand played so far, the
+    /// current one included: each hand contains the signal given with the
+    /// first card of every trick led, if any, in the order of the tricks.
+    pub fn signals(&self) -> &[Vec<Option<Signal>>] {
+        &self.signals
+    }
+
+    /// The signal given by the leader of the current trick, if any.
+    pub fn current_signal(&self) -> Option<Signal> {
+        self.game.current_trick()?.led()?;
+        let hand = self.signals.last()?;
+        hand.get(self.game.current_hand().index())
+            .copied()
+            .flatten()
+    }
+}
+
+impl Deref for TressetteGame {
+    type Target = Game<TressetteRules>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.game
+    }
+}
 
 impl From<ItalianCard> for TressetteCard {
     fn from(value: ItalianCard) -> Self {
@@ -259,18 +1339,29 @@ impl TressetteCard {
 }
 
 #[cfg(test)]
+#[allow(clippy::unwrap_used)]
 mod tests {
     use crate::{
         common::{
-            cards::{ItalianRank, Suit},
-            hands::{Player, PlayerId, TrickTakingGame},
+            cards::{Card, Deck, ItalianRank, Suit},
+            game::Phase,
+            hands::{
+                DeclarationError, Hand, HandContext, OngoingHand, OngoingTrick, Player, PlayerId,
+                TrickStrength, TrickTakingGame,
+            },
+            teams::Scoreboard,
         },
+        error::ShuftError,
         tressette::SCORE_TO_WIN,
     };
     use prop::collection::hash_set;
     use proptest::prelude::*;
+    use std::cmp::Ordering;
 
-    use super::{TressetteCard, TressetteRules};
+    use super::{
+        Accuso, Cappotto, CiapanoRules, ParseMoveError, Rounding, Signal, SignalError, TiePolicy,
+        TressetteCard, TressetteGame, TressetteMove, TressetteRules, SCORE_TO_LOSE,
+    };
 
     fn tressette_card_strategy() -> impl Strategy<Value = TressetteCard> {
         (
@@ -310,37 +1401,563 @@ mod tests {
             })
     }
 
+    /// A hand where every player plays the cards of a different suit, so
+    /// that player 0 takes every trick. Player 0 declares the whole suit as a
+    /// napoletana.
+    fn cappotto() -> Hand<TressetteRules> {
+        let mut players: Vec<Player<TressetteRules>> = (0..4)
+            .map(|i| Player::new(PlayerId::new(i).unwrap()))
+            .collect();
+        let deck = Deck::tressette();
+        for (suit, player) in players.iter_mut().enumerate() {
+            for card in &deck[suit * 10..suit * 10 + 10] {
+                player.give(*card);
+            }
+        }
+
+        let mut hand = OngoingHand::new(PlayerId::new(0).unwrap());
+        for accuso in Accuso::find(players[0].hand()) {
+            hand.declare(&players[0], accuso.cards()).unwrap();
+        }
+        for trick in 0..10 {
+            for (suit, player) in players.iter_mut().enumerate() {
+                hand.play(player, deck[suit * 10 + trick]).unwrap();
+            }
+        }
+        hand.finish().unwrap()
+    }
+
+    #[test]
+    fn only_the_leading_suit_takes() {
+        let cards = [
+            TressetteCard::new(ItalianRank::Four, Suit::Hearts),
+            TressetteCard::new(ItalianRank::Three, Suit::Clubs),
+            TressetteCard::new(ItalianRank::Two, Suit::Hearts),
+            TressetteCard::new(ItalianRank::Ace, Suit::Hearts),
+        ];
+
+        let context = HandContext::new();
+        let taker = TressetteRules::determine_taker(&cards, PlayerId::new(0).unwrap(), &context);
+        assert_eq!(*taker, 2);
+    }
+
+    #[test]
+    fn can_be_played_in_three() {
+        let first_to_play = PlayerId::<TressetteRules>::with_players(2, 3).unwrap();
+        let mut trick = OngoingTrick::new(first_to_play);
+        trick.play_unchecked(TressetteCard::new(ItalianRank::King, Suit::Clubs));
+        trick.play_unchecked(TressetteCard::new(ItalianRank::Two, Suit::Clubs));
+        trick.play_unchecked(TressetteCard::new(ItalianRank::Ace, Suit::Clubs));
+
+        let trick = trick.finish().unwrap();
+        assert_eq!(trick.players(), 3);
+        assert_eq!(trick.taker(), PlayerId::with_players(0, 3).unwrap());
+        assert!(PlayerId::<TressetteRules>::with_players(0, 5).is_none());
+    }
+
+    #[test]
+    fn variants_change_the_score() {
+        let hand = cappotto();
+        let score = |rules: TressetteRules, scores: Vec<i32>| {
+            let mut scoreboard = Scoreboard::from(scores);
+            rules.compute_score(&hand, &mut scoreboard);
+            scoreboard.scores().to_vec()
+        };
+
+        assert_eq!(score(TressetteRules::default(), vec![]), [11, 0]);
+        let rules = TressetteRules::default()
+            .with_rounding(Rounding::Nearest)
+            .with_last_trick_bonus(0);
+        assert_eq!(score(rules, vec![]), [11, 0]);
+        let rules = TressetteRules::default().with_rounding(Rounding::Nearest);
+        assert_eq!(score(rules, vec![]), [12, 0]);
+
+        let rules = TressetteRules::default().with_cappotto(Cappotto::WinsGame);
+        assert_eq!(score(rules.clone(), vec![5, 20]), [31, 20]);
+        assert_eq!(score(rules.clone(), vec![5, 33]), [34, 33]);
+        assert_eq!(score(rules, vec![25, 3]), [36, 3]);
+    }
+
+    #[test]
+    fn cappotto_ignores_the_last_trick() {
+        // Player 0 leads the hearts and takes every card worth points, while
+        // player 1 takes the last trick with the 5 of diamonds.
+        let cards = |suit, ranks: &[ItalianRank]| {
+            ranks
+                .iter()
+                .map(move |&rank| TressetteCard::new(rank, suit))
+                .collect::<Vec<_>>()
+        };
+        let hearts = cards(
+            Suit::Hearts,
+            &[
+                ItalianRank::Three,
+                ItalianRank::Two,
+                ItalianRank::Ace,
+                ItalianRank::King,
+                ItalianRank::Knight,
+                ItalianRank::Jack,
+                ItalianRank::Seven,
+                ItalianRank::Six,
+                ItalianRank::Five,
+            ],
+        );
+        let others = |suit| {
+            cards(
+                suit,
+                &[
+                    ItalianRank::Three,
+                    ItalianRank::Two,
+                    ItalianRank::Ace,
+                    ItalianRank::King,
+                    ItalianRank::Knight,
+                    ItalianRank::Jack,
+                    ItalianRank::Seven,
+                    ItalianRank::Six,
+                    ItalianRank::Five,
+                    ItalianRank::Four,
+                ],
+            )
+        };
+        // Player 1 follows the first trick with the 4 of hearts, then gives
+        // away the diamonds and keeps the 5 for the last trick.
+        let diamonds = std::iter::once(TressetteCard::new(ItalianRank::Four, Suit::Hearts))
+            .chain(others(Suit::Diamonds).into_iter().take(9))
+            .collect();
+        let four = TressetteCard::new(ItalianRank::Four, Suit::Diamonds);
+        let played = [
+            hearts.into_iter().chain([four]).collect::<Vec<_>>(),
+            diamonds,
+            others(Suit::Clubs),
+            others(Suit::Spades),
+        ];
+
+        let mut players: Vec<Player<TressetteRules>> = (0..4)
+            .map(|i| Player::new(PlayerId::new(i).unwrap()))
+            .collect();
+        for (player, cards) in players.iter_mut().zip(&played) {
+            for card in cards {
+                player.give(*card);
+            }
+        }
+        let mut hand = OngoingHand::new(PlayerId::new(0).unwrap());
+        for trick in 0..10 {
+            for (player, cards) in players.iter_mut().zip(&played) {
+                hand.play(player, cards[trick]).unwrap();
+            }
+        }
+        let hand = hand.finish().unwrap();
+        assert_eq!(*hand.tricks()[9].taker(), 1);
+
+        let rules = TressetteRules::default().with_cappotto(Cappotto::Bonus(11));
+        let mut scoreboard = Scoreboard::default();
+        rules.compute_score(&hand, &mut scoreboard);
+        assert_eq!(scoreboard.scores(), [21, 1]);
+    }
+
+    #[test]
+    fn accusi_are_declared_at_the_first_turn() {
+        let mut players: Vec<Player<TressetteRules>> = (0..4)
+            .map(|i| Player::new(PlayerId::new(i).unwrap()))
+            .collect();
+        for (i, player) in players.iter_mut().enumerate() {
+            let suit = Suit::from_repr(i).unwrap();
+            for rank in [ItalianRank::Ace, ItalianRank::Two, ItalianRank::Three] {
+                player.give(TressetteCard::new(rank, suit));
+            }
+        }
+        let napoletana = |i| Accuso::Napoletana {
+            suit: Suit::from_repr(i).unwrap(),
+            length: 3,
+        };
+
+        let mut hand = OngoingHand::<TressetteRules>::with_tricks(PlayerId::new(1).unwrap(), 3);
+        assert_eq!(
+            hand.declare(&players[0], napoletana(0).cards()),
+            Err(DeclarationError::NotFirstTurn.into())
+        );
+        assert_eq!(
+            hand.declare(&players[1], napoletana(0).cards()),
+            Err(DeclarationError::NotHeld.into())
+        );
+        // Only whole combinations can be declared.
+        let mut partial = napoletana(1).cards();
+        partial.remove(TressetteCard::new(
+            ItalianRank::Two,
+            Suit::from_repr(1).unwrap(),
+        ));
+        assert_eq!(
+            hand.declare(&players[1], partial),
+            Err(DeclarationError::NotHeld.into())
+        );
+        hand.declare(&players[1], napoletana(1).cards()).unwrap();
+        assert_eq!(
+            hand.declare(&players[1], napoletana(1).cards()),
+            Err(ShuftError::IllegalDeclaration(
+                DeclarationError::AlreadyDeclared
+            ))
+        );
+
+        // Once they played, players can't declare anymore.
+        let card = players[1].hand().iter().next().unwrap();
+        hand.play(&mut players[1], card).unwrap();
+        assert_eq!(
+            hand.declare(&players[1], napoletana(1).cards()),
+            Err(DeclarationError::NotFirstTurn.into())
+        );
+        hand.declare(&players[2], napoletana(2).cards()).unwrap();
+        assert_eq!(
+            hand.declarations(),
+            [
+                (PlayerId::new(1).unwrap(), napoletana(1).cards()),
+                (PlayerId::new(2).unwrap(), napoletana(2).cards())
+            ]
+        );
+
+        #[cfg(feature = "serde")]
+        {
+            let json = serde_json::to_string(&hand).unwrap();
+            let deserialized: OngoingHand<TressetteRules> = serde_json::from_str(&json).unwrap();
+            assert_eq!(deserialized.declarations(), hand.declarations());
+        }
+    }
+
+    #[test]
+    fn accusi_are_scored_when_enabled() {
+        let hand = cappotto();
+        let suit = Deck::tressette()[0].suit();
+        assert_eq!(
+            hand.accusi(),
+            [(
+                PlayerId::new(0).unwrap(),
+                Accuso::Napoletana { suit, length: 10 }
+            )]
+        );
+
+        let rules = TressetteRules::default().with_accusi(true);
+        let mut scoreboard = Scoreboard::default();
+        rules.compute_score(&hand, &mut scoreboard);
+        assert_eq!(scoreboard.scores(), [21, 0]);
+    }
+
+    #[test]
+    fn signals_depend_on_the_cards_of_the_suit() {
+        let hand = ["1C", "2C", "3H"]
+            .iter()
+            .map(|c| c.parse::<TressetteCard>().unwrap())
+            .collect();
+        let card = |c: &str| c.parse::<TressetteCard>().unwrap();
+
+        assert!(TressetteRules::can_signal(
+            &hand,
+            card("1C"),
+            Signal::Striscio
+        ));
+        assert!(!TressetteRules::can_signal(&hand, card("1C"), Signal::Volo));
+        assert!(TressetteRules::can_signal(&hand, card("3H"), Signal::Volo));
+        assert!(!TressetteRules::can_signal(
+            &hand,
+            card("3H"),
+            Signal::Striscio
+        ));
+        assert!(TressetteRules::can_signal(&hand, card("3H"), Signal::Busso));
+
+        assert_eq!(
+            "3C volo".parse::<TressetteMove>(),
+            Ok(TressetteMove::new(card("3C"), Some(Signal::Volo)))
+        );
+        assert_eq!(
+            "3C piombo".parse::<TressetteMove>(),
+            Err(ParseMoveError::InvalidSignal("piombo".to_string()))
+        );
+        assert!("3C busso volo".parse::<TressetteMove>().is_err());
+        assert!("".parse::<TressetteMove>().is_err());
+    }
+
+    #[test]
+    fn only_the_leader_signals() {
+        let mut players: Vec<Player<TressetteRules>> = (0..4)
+            .map(|i| Player::new(PlayerId::new(i).unwrap()))
+            .collect();
+        for (i, player) in players.iter_mut().enumerate() {
+            let suit = Suit::from_repr(i).unwrap();
+            for rank in [ItalianRank::Ace, ItalianRank::Two] {
+                player.give(TressetteCard::new(rank, suit));
+            }
+        }
+        let ace = |i| TressetteCard::new(ItalianRank::Ace, Suit::from_repr(i).unwrap());
+        let signaled = |i, signal| TressetteMove::new(ace(i), Some(signal));
+
+        let mut trick = OngoingTrick::new(PlayerId::new(1).unwrap());
+        assert_eq!(
+            TressetteRules::play_move(&mut players[2], signaled(2, Signal::Busso), &mut trick),
+            Err(SignalError::NotLeader.into())
+        );
+        // Player 1 still holds the two of the suit.
+        assert_eq!(
+            TressetteRules::play_move(&mut players[1], signaled(1, Signal::Volo), &mut trick),
+            Err(ShuftError::IllegalSignal(SignalError::NotAllowed))
+        );
+        assert_eq!(players[1].hand().len(), 2);
+        TressetteRules::play_move(&mut players[1], signaled(1, Signal::Striscio), &mut trick)
+            .unwrap();
+        assert_eq!(
+            TressetteRules::play_move(&mut players[2], signaled(2, Signal::Busso), &mut trick),
+            Err(SignalError::NotLeader.into())
+        );
+        TressetteRules::play_move(&mut players[2], ace(2).into(), &mut trick).unwrap();
+        assert_eq!(trick.led(), Some(ace(1)));
+    }
+
+    #[test]
+    fn games_keep_the_signals_of_the_leaders() {
+        let mut game = TressetteGame::new(TressetteRules::default(), 5).unwrap();
+        let card = game.legal_moves().iter().next().unwrap();
+        game.apply_move(TressetteMove::new(card, Some(Signal::Busso)))
+            .unwrap();
+        assert_eq!(game.current_signal(), Some(Signal::Busso));
+
+        let card = game.legal_moves().iter().next().unwrap();
+        let hand = *game.players()[*game.current_player().unwrap()].hand();
+        assert_eq!(
+            game.apply_move(TressetteMove::new(card, Some(Signal::Busso))),
+            Err(SignalError::NotLeader.into())
+        );
+        assert_eq!(
+            *game.players()[*game.current_player().unwrap()].hand(),
+            hand
+        );
+        while game.current_trick().unwrap().led().is_some() {
+            let card = game.legal_moves().iter().next().unwrap();
+            game.apply_move(card.into()).unwrap();
+        }
+        assert_eq!(game.current_signal(), None);
+
+        while game.phase() != Phase::Completed {
+            let card = game.legal_moves().iter().next().unwrap();
+            game.apply_move(card.into()).unwrap();
+        }
+        let signals = game.signals();
+        assert_eq!(signals.len(), game.hands().len());
+        assert!(signals.iter().all(|hand| hand.len() == 10));
+        assert_eq!(signals[0][0], Some(Signal::Busso));
+        assert_eq!(signals.iter().flatten().flatten().count(), 1);
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn saved_games_keep_the_signals() {
+        let mut game = TressetteGame::new(TressetteRules::default(), 8).unwrap();
+        let card = game.legal_moves().iter().next().unwrap();
+        game.apply_move(TressetteMove::new(card, Some(Signal::Busso)))
+            .unwrap();
+
+        let json = serde_json::to_string(&game).unwrap();
+        let mut deserialized: TressetteGame = serde_json::from_str(&json).unwrap();
+        assert_eq!(deserialized.signals(), game.signals());
+        assert_eq!(deserialized.current_signal(), Some(Signal::Busso));
+
+        // The saved game goes on like the original one.
+        while game.phase() != Phase::Completed {
+            let card = game.legal_moves().iter().next().unwrap();
+            game.apply_move(card.into()).unwrap();
+            deserialized.apply_move(card.into()).unwrap();
+        }
+        assert_eq!(deserialized.signals()[0][0], Some(Signal::Busso));
+        assert_eq!(deserialized.score(), game.score());
+        assert_eq!(
+            serde_json::to_string(&deserialized).unwrap(),
+            serde_json::to_string(&game).unwrap()
+        );
+    }
+
+    #[test]
+    fn sets_of_aces_twos_and_threes_are_found() {
+        let hand = ["1H", "1D", "1C", "1S", "2H", "2D", "3H", "3C", "3S", "4H"]
+            .iter()
+            .map(|c| c.parse().unwrap())
+            .collect();
+        assert_eq!(
+            Accuso::find(&hand),
+            [
+                Accuso::Napoletana {
+                    suit: Suit::Hearts,
+                    length: 4
+                },
+                Accuso::Set {
+                    rank: ItalianRank::Ace,
+                    missing: None
+                },
+                Accuso::Set {
+                    rank: ItalianRank::Three,
+                    missing: Some(Suit::Diamonds)
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn ties_can_end_in_a_draw() {
+        let tied = Scoreboard::from(vec![41, 41]);
+        let rules = TressetteRules::default().with_target(41);
+        assert!(!rules.is_completed(&tied));
+
+        let rules = rules.with_tie_policy(TiePolicy::Draw);
+        assert!(rules.is_completed(&tied));
+        assert_eq!(rules.winner(&tied), None);
+        assert!(!rules.is_completed(&Scoreboard::from(vec![40, 40])));
+        assert_eq!(rules.winner(&Scoreboard::from(vec![42, 41])), Some(0));
+    }
+
+    #[test]
+    fn ciapano_is_scored_by_player() {
+        let card = |rank, suit| TressetteCard::new(rank, Suit::from_repr(suit).unwrap());
+        let hands = [
+            [card(ItalianRank::Three, 0), card(ItalianRank::Five, 0)],
+            [card(ItalianRank::Ace, 0), card(ItalianRank::Two, 0)],
+            [card(ItalianRank::Four, 1), card(ItalianRank::Five, 1)],
+            [card(ItalianRank::Four, 2), card(ItalianRank::Five, 2)],
+        ];
+        let mut players: Vec<Player<CiapanoRules>> = (0..4)
+            .map(|i| Player::new(PlayerId::new(i).unwrap()))
+            .collect();
+        for (player, cards) in players.iter_mut().zip(hands) {
+            cards.into_iter().for_each(|c| player.give(c));
+        }
+
+        // Player 0 takes the first trick with the three, player 1 takes the
+        // second one with the two.
+        let mut hand = OngoingHand::with_tricks(PlayerId::new(0).unwrap(), 2);
+        for trick in 0..2 {
+            for (player, cards) in players.iter_mut().zip(hands) {
+                hand.play(player, cards[trick]).unwrap();
+            }
+        }
+        let hand = hand.finish().unwrap();
+        assert_eq!(*hand.tricks()[1].taker(), 1);
+
+        let mut scoreboard = Scoreboard::default();
+        CiapanoRules::default().compute_score(&hand, &mut scoreboard);
+        assert_eq!(scoreboard.scores(), [1, 1, 0, 0]);
+        assert!(!CiapanoRules::default().is_completed(&scoreboard));
+    }
+
+    #[test]
+    fn ciapano_ties_for_the_lowest_score_are_played_out() {
+        let rules = CiapanoRules::default().with_limit(21);
+        assert!(!rules.is_completed(&Scoreboard::from(vec![20, 3, 9, 14])));
+        assert!(!rules.is_completed(&Scoreboard::from(vec![24, 3, 3, 14])));
+        assert_eq!(rules.winner(&Scoreboard::from(vec![24, 3, 4, 14])), Some(1));
+    }
+
     proptest! {
         #[test]
-        fn a_team_won_with_both_below(team1_score in 0u8..SCORE_TO_WIN, team2_score in 0u8..SCORE_TO_WIN) {
-            let result = TressetteRules::is_completed((team1_score, team2_score));
+        fn strength_is_antisymmetric(led in tressette_card_strategy(), a in tressette_card_strategy(), b in tressette_card_strategy()) {
+            let context = HandContext::new();
+            prop_assert_eq!(TressetteRules::compare(&led, &a, &b, &context), TressetteRules::compare(&led, &b, &a, &context).reverse());
+            if a != b && a.suit() == led.suit() && b.suit() == led.suit() {
+                prop_assert_ne!(TressetteRules::compare(&led, &a, &b, &context), Ordering::Equal);
+            }
+        }
+
+        #[test]
+        fn the_taker_played_the_strongest_card(cards in hash_set(tressette_card_strategy(), 4), first in 0usize..4) {
+            let cards: Vec<_> = cards.into_iter().collect();
+            let first = PlayerId::new(first).unwrap();
+            let context = HandContext::new();
+            let taker = TressetteRules::determine_taker(&cards, first, &context);
+            let led = cards[*first];
+            for card in &cards {
+                prop_assert_ne!(TressetteRules::compare(&led, card, &cards[*taker], &context), Ordering::Greater);
+            }
+        }
+
+        #[test]
+        fn a_team_won_with_both_below(team1_score in 0..SCORE_TO_WIN, team2_score in 0..SCORE_TO_WIN) {
+            let result = TressetteRules::default().is_completed(&Scoreboard::from(vec![team1_score, team2_score]));
             assert!(!result);
         }
 
         #[test]
-        fn a_team_won_with_both_above_and_same(score in SCORE_TO_WIN..u8::MAX) {
-            let result = TressetteRules::is_completed((score, score));
+        fn a_team_won_with_both_above_and_same(score in SCORE_TO_WIN..255) {
+            let result = TressetteRules::default().is_completed(&Scoreboard::from(vec![score, score]));
             assert!(!result);
         }
 
         #[test]
-        fn a_team_won_with_both_above_and_different(score in SCORE_TO_WIN..u8::MAX) {
-            let result = TressetteRules::is_completed((score, score+1));
+        fn a_team_won_with_both_above_and_different(score in SCORE_TO_WIN..255) {
+            let result = TressetteRules::default().is_completed(&Scoreboard::from(vec![score, score + 1]));
             assert!(result);
         }
 
         #[test]
-        fn a_team_won_with_team1_above(team1_score in 0u8..SCORE_TO_WIN, team2_score in SCORE_TO_WIN..u8::MAX) {
-            let result = TressetteRules::is_completed((team1_score, team2_score));
+        fn a_team_won_with_team1_above(team1_score in 0..SCORE_TO_WIN, team2_score in SCORE_TO_WIN..255) {
+            let result = TressetteRules::default().is_completed(&Scoreboard::from(vec![team1_score, team2_score]));
             assert!(result);
         }
 
         #[test]
-        fn a_team_won_with_team2_above(team1_score in SCORE_TO_WIN..u8::MAX, team2_score in 0u8..SCORE_TO_WIN ) {
-            let result = TressetteRules::is_completed((team1_score, team2_score));
+        fn a_team_won_with_team2_above(team1_score in SCORE_TO_WIN..255, team2_score in 0..SCORE_TO_WIN ) {
+            let result = TressetteRules::default().is_completed(&Scoreboard::from(vec![team1_score, team2_score]));
             assert!(result);
         }
 
+        #[test]
+        fn a_player_won_in_three(scores in prop::array::uniform3(0..SCORE_TO_WIN)) {
+            let mut scoreboard = Scoreboard::from(scores.to_vec());
+            prop_assert!(!TressetteRules::default().is_completed(&scoreboard));
+            scoreboard.add(1, SCORE_TO_WIN);
+            prop_assert!(TressetteRules::default().is_completed(&scoreboard));
+        }
+
+        #[test]
+        fn ciapano_is_lost_at_the_limit(scores in prop::array::uniform4(0..SCORE_TO_LOSE), loser in 0usize..4) {
+            let mut scoreboard = Scoreboard::from(scores.to_vec());
+            let rules = CiapanoRules::default();
+            prop_assert!(!rules.is_completed(&scoreboard));
+            scoreboard.add(loser, SCORE_TO_LOSE);
+            if let Some(winner) = rules.winner(&scoreboard) {
+                prop_assert_ne!(winner, loser);
+                prop_assert!(scoreboard.scores().iter().all(|&s| s >= scoreboard.score(winner)));
+            }
+        }
+
+        #[test]
+        fn the_target_can_be_changed(target in prop_oneof![Just(21), Just(31), Just(41)], score in 0..60) {
+            let rules = TressetteRules::default().with_target(target);
+            let scoreboard = Scoreboard::from(vec![score, 0]);
+            prop_assert_eq!(rules.is_completed(&scoreboard), score >= target);
+            prop_assert_eq!(rules.winner(&scoreboard).is_some(), score >= target);
+        }
+
+        #[test]
+        fn accusi_are_worth_their_cards(player in player_strategy()) {
+            for accuso in Accuso::find(player.hand()) {
+                prop_assert!(accuso.cards().iter().all(|c| player.hand().contains(c)));
+                prop_assert_eq!(accuso.points(), accuso.cards().len() as i32);
+            }
+        }
+
+        #[test]
+        fn move_notation_round_trips(
+            card in tressette_card_strategy(),
+            signal in prop::option::of(prop_oneof![Just(Signal::Busso), Just(Signal::Striscio), Just(Signal::Volo)]),
+        ) {
+            let mv = TressetteMove::new(card, signal);
+            prop_assert_eq!(mv.to_string().parse::<TressetteMove>(), Ok(mv));
+        }
+
+        #[test]
+        fn parse_round_trips_display(card in tressette_card_strategy()) {
+            prop_assert_eq!(card.to_string().parse::<TressetteCard>(), Ok(card));
+        }
+
+        #[test]
+        fn index_round_trips(card in tressette_card_strategy()) {
+            prop_assert!(card.index() < TressetteCard::DECK_SIZE);
+            prop_assert_eq!(TressetteCard::from_index(card.index()), Some(card));
+        }
+
         #[test]
         fn playable_works(player in player_strategy(), suit in prop_oneof![Just(Suit::Hearts), Just(Suit::Spades), Just(Suit::Clubs), Just(Suit::Diamonds)]) {
             let playable = TressetteRules::playable(&player, None);
@@ -1,58 +1,82 @@
-#![feature(generic_const_exprs)]
+//! Plays complete games of tressette through the public API.
 use shuftlib::{
-    common::{
-        cards::Deck,
-        hands::{OngoingHand, OngoingTrick, Player, PlayerId, TrickTakingGame},
-    },
-    tressette::{self, TressetteCard, TressetteRules},
+    common::game::{Game, Phase},
+    tressette::{self, CiapanoRules, Signal, TressetteGame, TressetteMove, TressetteRules},
 };
 
 #[test]
 #[allow(clippy::unwrap_used)]
 fn tressette_works() {
-    let mut first = true;
-    let mut leading_suit = None;
-    let first_to_play = PlayerId::new(0).unwrap();
-    let mut score = (0, 0);
-    let mut players = [
-        Player::new(PlayerId::new(0).unwrap()),
-        Player::new(PlayerId::new(1).unwrap()),
-        Player::new(PlayerId::new(2).unwrap()),
-        Player::new(PlayerId::new(3).unwrap()),
-    ];
-    let mut hands = Vec::new();
-
-    while !TressetteRules::is_completed(score) {
-        let mut ongoing_hand = OngoingHand::<TressetteRules>::new();
-        let mut deck = Deck::italian();
-        deck.shuffle();
-
-        for (i, &card) in deck.iter().enumerate() {
-            let player_index = (i / 5) % TressetteRules::PLAYERS;
-            players[player_index].give(TressetteCard::from(card));
+    for seed in 0..10 {
+        let mut game = Game::new(TressetteRules::default(), seed).unwrap();
+        while game.phase() != Phase::Completed {
+            let card = game.legal_moves().iter().next().unwrap();
+            game.apply(card).unwrap();
         }
 
-        for trick_id in 0..TressetteRules::TRICKS {
-            let mut ongoing_trick = OngoingTrick::<TressetteRules>::new(first_to_play);
-            for _ in 0..TressetteRules::PLAYERS {
-                let next_to_play = ongoing_trick.next_to_play();
-                let playable = TressetteRules::playable(&players[*next_to_play], leading_suit);
-                TressetteRules::play(&mut players[*next_to_play], playable[0], &mut ongoing_trick);
-
-                if first {
-                    leading_suit = Some(playable[0].suit());
-                    first = !first;
-                }
-            }
-            first = !first;
-            ongoing_hand.add(ongoing_trick.finish().unwrap(), trick_id);
+        let score = game.score();
+        assert_eq!(score.scores().len(), 2);
+        assert_eq!(score.total() as usize, game.hands().len() * 11);
+        let winner = score.leader().unwrap();
+        assert!(score.score(winner) >= tressette::SCORE_TO_WIN);
+    }
+}
+
+#[test]
+#[allow(clippy::unwrap_used)]
+fn tressette_can_be_played_in_two_and_three() {
+    for players in 2..=3 {
+        let mut game = Game::with_players(TressetteRules::default(), players, 1).unwrap();
+        let tricks = 40 / players;
+        // In two, players receive 10 cards and draw the other ones.
+        let cards = if players == 2 { 10 } else { tricks };
+        assert_eq!(game.players()[0].hand().len(), cards);
+        assert_eq!(game.stock().map(|s| s.len()), (players == 2).then_some(20));
+
+        while game.phase() != Phase::Completed {
+            let card = game.legal_moves().iter().next().unwrap();
+            game.apply(card).unwrap();
         }
-        let hand = ongoing_hand.finish().unwrap();
-        TressetteRules::compute_score(&hand, &mut score);
-        hands.push(hand);
+        assert!(game.hands().iter().all(|h| h.tricks().len() == tricks));
+        // Every player plays for themselves.
+        assert!(game.score().scores().len() <= players);
+        assert!(game.score().leader().is_some());
+    }
+}
+
+#[test]
+#[allow(clippy::unwrap_used)]
+fn leaders_can_signal() {
+    let mut game = TressetteGame::new(TressetteRules::default(), 3).unwrap();
+    while game.phase() != Phase::Completed {
+        let card = game.legal_moves().iter().next().unwrap();
+        let leads = game.current_trick().unwrap().led().is_none();
+        let mv = TressetteMove::new(card, leads.then_some(Signal::Busso));
+        game.apply_move(mv.to_string().parse().unwrap()).unwrap();
     }
 
-    assert_eq!((score.0 + score.1) % 11, 0);
-    assert_ne!(score.0, score.1);
-    assert!(score.0 >= tressette::SCORE_TO_WIN || score.1 >= tressette::SCORE_TO_WIN);
+    let tricks = game.hands().iter().map(|h| h.tricks().len()).sum::<usize>();
+    let signals = game.signals().iter().flatten();
+    assert_eq!(signals.clone().count(), tricks);
+    assert!(signals.into_iter().all(|s| *s == Some(Signal::Busso)));
+}
+
+#[test]
+#[allow(clippy::unwrap_used)]
+fn ciapano_works() {
+    for players in 3..=4 {
+        let rules = CiapanoRules::default();
+        let mut game = Game::with_players(rules.clone(), players, 7).unwrap();
+        while game.phase() != Phase::Completed {
+            let card = game.legal_moves().iter().next().unwrap();
+            game.apply(card).unwrap();
+        }
+
+        // Every player has their own score, and the lowest one wins.
+        let score = game.score();
+        assert_eq!(score.scores().len(), players);
+        assert!(score.scores().iter().any(|&s| s >= rules.limit()));
+        let winner = rules.winner(score).unwrap();
+        assert!(score.scores().iter().all(|&s| s >= score.score(winner)));
+    }
 }